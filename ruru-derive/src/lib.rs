@@ -0,0 +1,184 @@
+//! Procedural macros for exposing Rust structs as Ruby classes with
+//! [ruru](https://github.com/d-unseductable/ruru)
+//!
+//! The macros are re-exported by ruru when the `derive` feature is enabled:
+//!
+//! ```toml
+//! ruru = { version = "0.9", features = ["derive"] }
+//! ```
+//!
+//!  - `#[derive(RubyClass)]` makes a struct wrappable into Ruby objects of the class with the
+//!    same name. It implements `TypedData` and `ToRuby` for the struct, so values returned
+//!    to Ruby are wrapped automatically.
+//!
+//!  - `#[ruby_methods]` exposes functions of an `impl` block as methods of the class and
+//!    generates `define_class()` function, which defines the class with all the methods.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate ruru;
+//!
+//! use ruru::{ruby_methods, AnyObject, Class, Fixnum, Object, RubyClass, ToRuby, VM};
+//!
+//! #[derive(Default, RubyClass)]
+//! #[ruby_class(allocator)]
+//! pub struct Counter {
+//!     count: i64,
+//! }
+//!
+//! #[ruby_methods]
+//! impl Counter {
+//!     fn initialize(&mut self, start: i64) {
+//!         self.count = start;
+//!     }
+//!
+//!     fn increment(&mut self, step: Option<i64>) -> i64 {
+//!         self.count += step.unwrap_or(1);
+//!         self.count
+//!     }
+//!
+//!     fn count(&self) -> i64 {
+//!         self.count
+//!     }
+//!
+//!     #[ruby(name = "zero?")]
+//!     fn is_zero(&self) -> bool {
+//!         self.count == 0
+//!     }
+//!
+//!     // Functions without `self` are defined as singleton methods
+//!     fn zero() -> Counter {
+//!         Counter { count: 0 }
+//!     }
+//! }
+//!
+//! fn main() {
+//!     # VM::init();
+//!     Counter::define_class();
+//!
+//!     let counter = Counter { count: 1 }.to_ruby();
+//!
+//!     counter.send("increment", Some(&[Fixnum::new(2).to_any_object()]));
+//!
+//!     let count = counter.send("count", None).try_convert_to::<Fixnum>();
+//!
+//!     assert_eq!(count, Ok(Fixnum::new(3)));
+//! }
+//! ```
+//!
+//! Ruby:
+//!
+//! ```ruby
+//! class Counter
+//!   def initialize(start)
+//!     @count = start
+//!   end
+//!
+//!   def increment(step)
+//!     @count += step || 1
+//!   end
+//!
+//!   def count
+//!     @count
+//!   end
+//!
+//!   def zero?
+//!     @count == 0
+//!   end
+//!
+//!   def self.zero
+//!     new(0)
+//!   end
+//! end
+//!
+//! counter = Counter.new(1)
+//! counter.increment(2) == 3
+//! ```
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+mod methods;
+mod ruby_class;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, Error, ItemImpl};
+
+/// Makes a struct wrappable into Ruby objects.
+///
+/// The struct must not have generic parameters. The macro implements:
+///
+///  - `TypedData` with the class of the same name as the struct;
+///  - `ToRuby`, which wraps the struct into a new object of the class.
+///
+/// The class itself is defined by `define_class()` generated by `#[ruby_methods]`.
+///
+/// # Options
+///
+/// Options are passed with the `#[ruby_class(...)]` attribute:
+///
+///  - `name = "Name"` sets the name of the Ruby class;
+///
+///  - `allocator` allows to create objects with `Class#new` in Ruby. The struct must implement
+///    `Default`, the default value is wrapped and `initialize` method is called. Without the
+///    option, objects can be created only from Rust;
+///
+///  - `mark = "function"` sets a method of the struct which marks Ruby objects contained in the
+///    struct with `GC::mark()` (see `TypedData::mark()`).
+///
+/// See the crate documentation for examples.
+#[proc_macro_derive(RubyClass, attributes(ruby_class))]
+pub fn derive_ruby_class(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    ruby_class::expand(&input)
+        .unwrap_or_else(compile_errors)
+        .into()
+}
+
+/// Exposes functions of an `impl` block as Ruby methods.
+///
+/// The struct must derive `RubyClass`. The macro generates `define_class()` function, which
+/// defines the Ruby class with the methods and returns it:
+///
+///  - functions with `&self` or `&mut self` are defined as instance methods. The data is
+///    borrowed from the object with `Object::with_borrow()` or `Object::with_borrow_mut()`;
+///
+///  - functions without `self` are defined as singleton methods.
+///
+/// Arguments of the functions are converted from Ruby objects with `FromRuby` and returned
+/// values are converted with `ToRuby`. `ArgumentError` is raised if the number of arguments
+/// does not match, conversion errors are raised as `TypeError` or `RangeError`. Panics are
+/// raised as `RuntimeError`.
+///
+/// # Options
+///
+/// Options are passed with the `#[ruby(...)]` attribute of a function:
+///
+///  - `name = "name"` sets the name of the Ruby method (e.g. `"empty?"` or `"count="`);
+///
+///  - `skip` leaves the function in Rust only.
+///
+/// See the crate documentation for examples.
+#[proc_macro_attribute]
+pub fn ruby_methods(_arguments: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemImpl);
+
+    methods::expand(input).unwrap_or_else(compile_errors).into()
+}
+
+// `Error::to_compile_error()` refers to `::core`, which cannot be used in Rust 2015 crates
+fn compile_errors(error: Error) -> proc_macro2::TokenStream {
+    let errors = error.into_iter().map(|error| {
+        let message = error.to_string();
+
+        quote_spanned!(error.span() => compile_error!(#message);)
+    });
+
+    quote! { #(#errors)* }
+}
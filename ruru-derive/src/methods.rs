@@ -0,0 +1,226 @@
+use proc_macro2::TokenStream;
+use syn::{
+    Attribute, Error, FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, LitStr, ReceiverKind, Result,
+    Type,
+};
+
+enum Receiver {
+    None,
+    Shared,
+    Mutable,
+}
+
+struct Options {
+    name: Option<String>,
+    skip: bool,
+}
+
+impl Options {
+    // Options are removed from the attributes, because `ruby` is not a real attribute
+    fn take(attributes: &mut Vec<Attribute>) -> Result<Options> {
+        let mut options = Options {
+            name: None,
+            skip: false,
+        };
+        let mut result = Ok(());
+
+        attributes.retain(|attribute| {
+            if !attribute.path().is_ident("ruby") {
+                return true;
+            }
+
+            let parsed = attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let name: LitStr = meta.value()?.parse()?;
+
+                    options.name = Some(name.value());
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("unknown option of `ruby`"));
+                }
+
+                Ok(())
+            });
+
+            if result.is_ok() {
+                result = parsed;
+            }
+
+            false
+        });
+
+        result.map(|_| options)
+    }
+}
+
+struct Method {
+    name: String,
+    wrapper: Ident,
+    definition: TokenStream,
+    receiver: Receiver,
+}
+
+impl Method {
+    fn new(
+        self_ty: &Type,
+        type_name: &Ident,
+        function: &ImplItemFn,
+        name: Option<String>,
+    ) -> Result<Method> {
+        let signature = &function.sig;
+        let ident = &signature.ident;
+
+        if !signature.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &signature.generics,
+                "generic functions cannot be exposed to Ruby",
+            ));
+        }
+
+        let mut receiver = Receiver::None;
+        let mut types = Vec::new();
+
+        for input in &signature.inputs {
+            match *input {
+                FnArg::Receiver(ref self_argument) => {
+                    receiver = match self_argument.kind {
+                        ReceiverKind::Reference(_, _, None) => Receiver::Shared,
+                        ReceiverKind::Reference(_, _, Some(_)) => Receiver::Mutable,
+                        _ => {
+                            return Err(Error::new_spanned(
+                                self_argument,
+                                "only `&self` and `&mut self` can be used in Ruby methods",
+                            ))
+                        }
+                    };
+                }
+                FnArg::Typed(ref argument) => types.push(argument.ty.clone()),
+            }
+        }
+
+        let wrapper = format_ident!("__ruru_{}_{}", type_name, ident);
+        let count = types.len();
+        let arguments: Vec<Ident> = (0..count)
+            .map(|i| format_ident!("argument_{}", i))
+            .collect();
+        let indices = 0..count;
+
+        let call = match receiver {
+            Receiver::None => quote! {
+                ::ruru::ToRuby::to_ruby(<#self_ty>::#ident(#(#arguments),*))
+            },
+            Receiver::Shared => quote! {
+                ::ruru::Object::with_borrow(&itself, |data: &#self_ty| {
+                    ::ruru::ToRuby::to_ruby(<#self_ty>::#ident(data, #(#arguments),*))
+                })
+            },
+            Receiver::Mutable => quote! {
+                ::ruru::Object::with_borrow_mut(&itself, |data: &mut #self_ty| {
+                    ::ruru::ToRuby::to_ruby(<#self_ty>::#ident(data, #(#arguments),*))
+                })
+            },
+        };
+
+        // The data is borrowed only while the function is called and it is released before
+        // exceptions raised by the function are propagated. Panics are raised as exceptions.
+        let definition = quote! {
+            #[allow(non_snake_case, unused_variables)]
+            extern "C" fn #wrapper(
+                argc: ::ruru::types::Argc,
+                argv: *const ::ruru::AnyObject,
+                itself: ::ruru::AnyObject,
+            ) -> ::ruru::AnyObject {
+                ::ruru::codegen::method(|| {
+                    let arguments = ::ruru::codegen::parse_arguments(argc, argv, #count);
+
+                    #(let #arguments = ::ruru::codegen::convert::<#types>(&arguments[#indices]);)*
+
+                    #call
+                })
+            }
+        };
+
+        Ok(Method {
+            name: name.unwrap_or_else(|| ident.to_string()),
+            wrapper: wrapper,
+            definition: definition,
+            receiver: receiver,
+        })
+    }
+
+    fn registration(&self) -> TokenStream {
+        let name = &self.name;
+        let wrapper = &self.wrapper;
+
+        match self.receiver {
+            Receiver::None => quote! { ::ruru::Object::def_self(&mut class, #name, #wrapper); },
+            _ => quote! { ::ruru::Object::def(&mut class, #name, #wrapper); },
+        }
+    }
+}
+
+fn type_name(self_ty: &Type) -> Result<Ident> {
+    if let Type::Path(ref path) = *self_ty {
+        if let Some(segment) = path.path.segments.last() {
+            if segment.arguments.is_empty() {
+                return Ok(segment.ident.clone());
+            }
+        }
+    }
+
+    Err(Error::new_spanned(
+        self_ty,
+        "#[ruby_methods] can be used only for structs without generic parameters",
+    ))
+}
+
+pub fn expand(mut input: ItemImpl) -> Result<TokenStream> {
+    if let Some((ref path, _)) = input.trait_ {
+        return Err(Error::new_spanned(
+            path,
+            "#[ruby_methods] cannot be used for trait implementations",
+        ));
+    }
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "#[ruby_methods] cannot be used for generic implementations",
+        ));
+    }
+
+    let self_ty = input.self_ty.clone();
+    let type_name = type_name(&self_ty)?;
+    let mut methods = Vec::new();
+
+    for item in &mut input.items {
+        if let ImplItem::Fn(ref mut function) = *item {
+            let options = Options::take(&mut function.attrs)?;
+
+            if !options.skip {
+                methods.push(Method::new(&self_ty, &type_name, function, options.name)?);
+            }
+        }
+    }
+
+    let definitions = methods.iter().map(|method| &method.definition);
+    let registrations = methods.iter().map(Method::registration);
+
+    Ok(quote! {
+        #input
+
+        #(#definitions)*
+
+        impl #self_ty {
+            /// Defines the Ruby class with methods of the struct and returns it.
+            pub fn define_class() -> ::ruru::Class {
+                let mut class = <#self_ty>::__ruru_new_class();
+
+                #(#registrations)*
+
+                class
+            }
+        }
+    })
+}
@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use syn::{DeriveInput, Error, Ident, LitStr, Result};
+
+struct Options {
+    name: Option<String>,
+    allocator: bool,
+    mark: Option<Ident>,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> Result<Options> {
+        let mut options = Options {
+            name: None,
+            allocator: false,
+            mark: None,
+        };
+
+        for attribute in &input.attrs {
+            if !attribute.path().is_ident("ruby_class") {
+                continue;
+            }
+
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let name: LitStr = meta.value()?.parse()?;
+
+                    options.name = Some(name.value());
+                } else if meta.path.is_ident("allocator") {
+                    options.allocator = true;
+                } else if meta.path.is_ident("mark") {
+                    let mark: LitStr = meta.value()?.parse()?;
+
+                    options.mark = Some(mark.parse()?);
+                } else {
+                    return Err(meta.error("unknown option of `ruby_class`"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "RubyClass cannot be derived for generic types",
+        ));
+    }
+
+    let options = Options::parse(input)?;
+    let ident = &input.ident;
+    let name = options.name.unwrap_or_else(|| ident.to_string());
+    let data_type_name = format!("Ruru/{}\0", ident);
+
+    let mark = options.mark.map(|mark| {
+        quote! {
+            fn mark(&self) {
+                #ident::#mark(self)
+            }
+        }
+    });
+
+    let allocator = if options.allocator {
+        quote! { ::ruru::Class::define_allocator::<#ident>(&mut class); }
+    } else {
+        quote! { ::ruru::Class::undef_allocator(&mut class); }
+    };
+
+    Ok(quote! {
+        impl ::ruru::TypedData for #ident {
+            fn class() -> ::ruru::Class {
+                ::ruru::Class::from_existing(#name)
+            }
+
+            fn data_type() -> &'static ::ruru::types::DataType {
+                static DATA_TYPE: ::ruru::codegen::StaticDataType =
+                    ::ruru::codegen::StaticDataType::new::<#ident>(#data_type_name);
+
+                DATA_TYPE.get()
+            }
+
+            #mark
+        }
+
+        impl ::ruru::ToRuby for #ident {
+            fn to_ruby(self) -> ::ruru::AnyObject {
+                ::ruru::TypedData::wrap(self)
+            }
+        }
+
+        impl #ident {
+            // Called by `define_class()` generated by `#[ruby_methods]`
+            #[doc(hidden)]
+            pub fn __ruru_new_class() -> ::ruru::Class {
+                let mut class = ::ruru::Class::new(#name, None);
+
+                #allocator
+
+                class
+            }
+        }
+    })
+}
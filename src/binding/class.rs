@@ -1,11 +1,65 @@
+use std::mem;
+use std::ptr;
+
+use ruby_sys::types::RBasic;
 use ruby_sys::{class, typed_data};
 
+use binding::global::rb_cObject;
 use binding::util as binding_util;
+use binding::vm;
 use typed_data::DataTypeWrapper;
-use types::{c_void, Callback, CallbackPtr, Value};
+use types::{c_char, c_int, c_void, Callback, CallbackPtr, DataType, Id, Value};
 use util;
 
-use Object;
+use {AnyObject, Object};
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_define_alloc_func(klass: Value, func: extern "C" fn(Value) -> Value);
+    fn rb_undef_alloc_func(klass: Value);
+    fn rb_typeddata_is_kind_of(object: Value, data_type: *const DataType) -> c_int;
+    fn rb_obj_is_kind_of(object: Value, klass: Value) -> Value;
+    fn rb_class_inherited_p(module: Value, ancestor: Value) -> Value;
+    fn rb_class2name(klass: Value) -> *const c_char;
+    fn rb_get_alloc_func(klass: Value) -> Option<extern "C" fn(Value) -> Value>;
+    fn rb_method_basic_definition_p(klass: Value, id: Id) -> c_int;
+    fn rb_obj_instance_eval(argc: c_int, argv: *const Value, object: Value) -> Value;
+    fn rb_define_private_method(klass: Value, name: *const c_char, func: CallbackPtr, argc: c_int);
+    fn rb_define_protected_method(
+        klass: Value,
+        name: *const c_char,
+        func: CallbackPtr,
+        argc: c_int,
+    );
+    fn rb_define_alias(klass: Value, new_name: *const c_char, old_name: *const c_char);
+    fn rb_undef_method(klass: Value, name: *const c_char);
+    fn rb_remove_method(klass: Value, name: *const c_char);
+}
+
+// Layout of the public `struct RData` from `ruby.h`, which is used to replace the data pointer
+// like `DATA_PTR(object) = data` does in C
+#[repr(C)]
+struct RData {
+    basic: RBasic,
+    dmark: *const c_void,
+    dfree: *const c_void,
+    data: *mut c_void,
+}
+
+// Data wrapped with `wrap_data()` starts with a header, because public C API does not provide
+// the data type of an object. The data type is needed to convert the data of child structs to
+// parent structs in `get_data()`.
+#[repr(C)]
+struct Header {
+    data_type: *const DataType,
+    data: *mut c_void,
+}
+
+#[repr(C)]
+struct WrappedData<T> {
+    header: Header,
+    data: T,
+}
 
 pub fn define_class(name: &str, superclass: Value) -> Value {
     let name = util::str_to_cstring(name);
@@ -67,6 +121,22 @@ pub fn define_attribute(object: Value, name: &str, reader: bool, writer: bool) {
     unsafe { class::rb_define_attr(object, name.as_ptr(), reader, writer) };
 }
 
+pub fn is_kind_of(object: Value, klass: Value) -> bool {
+    unsafe { rb_obj_is_kind_of(object, klass).is_true() }
+}
+
+pub fn is_inherited(klass: Value, ancestor: Value) -> bool {
+    unsafe { rb_class_inherited_p(klass, ancestor).is_true() }
+}
+
+pub fn class_name(klass: Value) -> String {
+    unsafe { util::cstr_to_string(rb_class2name(klass)) }
+}
+
+pub fn instance_eval(object: Value, code: Value) -> Value {
+    unsafe { rb_obj_instance_eval(1, &code as *const Value, object) }
+}
+
 pub fn respond_to(object: Value, method: &str) -> bool {
     let result = unsafe { class::rb_respond_to(object, binding_util::internal_id(method)) };
 
@@ -81,6 +151,49 @@ pub fn define_method<I: Object, O: Object>(klass: Value, name: &str, callback: C
     }
 }
 
+pub fn define_private_method<I: Object, O: Object>(
+    klass: Value,
+    name: &str,
+    callback: Callback<I, O>,
+) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_define_private_method(klass, name.as_ptr(), callback as CallbackPtr, -1);
+    }
+}
+
+pub fn define_protected_method<I: Object, O: Object>(
+    klass: Value,
+    name: &str,
+    callback: Callback<I, O>,
+) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_define_protected_method(klass, name.as_ptr(), callback as CallbackPtr, -1);
+    }
+}
+
+pub fn define_alias(klass: Value, new_name: &str, old_name: &str) {
+    let new_name = util::str_to_cstring(new_name);
+    let old_name = util::str_to_cstring(old_name);
+
+    unsafe { rb_define_alias(klass, new_name.as_ptr(), old_name.as_ptr()) };
+}
+
+pub fn undef_method(klass: Value, name: &str) {
+    let name = util::str_to_cstring(name);
+
+    unsafe { rb_undef_method(klass, name.as_ptr()) };
+}
+
+pub fn remove_method(klass: Value, name: &str) {
+    let name = util::str_to_cstring(name);
+
+    unsafe { rb_remove_method(klass, name.as_ptr()) };
+}
+
 pub fn define_singleton_method<I: Object, O: Object>(
     klass: Value,
     name: &str,
@@ -94,19 +207,152 @@ pub fn define_singleton_method<I: Object, O: Object>(
 }
 
 pub fn wrap_data<T>(klass: Value, data: T, wrapper: &DataTypeWrapper<T>) -> Value {
-    let data = Box::into_raw(Box::new(data)) as *mut c_void;
+    if let Some(class) = wrapper.class() {
+        if !is_inherited(klass, class.value()) {
+            let message = format!(
+                "{} is not {} or its subclass",
+                class_name(klass),
+                class_name(class.value())
+            );
 
-    unsafe { typed_data::rb_data_typed_object_wrap(klass, data, wrapper.data_type()) }
+            let exception = unsafe { binding_util::get_constant("TypeError", rb_cObject) };
+
+            drop(data);
+            vm::raise(exception, &message);
+
+            unreachable!()
+        }
+    }
+
+    if let Some((allocator, initialize_copy)) = wrapper.copy_functions() {
+        define_copy_functions(klass, allocator, initialize_copy);
+    }
+
+    let data = new_wrapped_data(data, wrapper.data_type());
+
+    wrap_typed_data(klass, data, wrapper.data_type())
+}
+
+// The allocator creates objects without data, which is set later by `initialize_copy`.
+// `allocate` and the default `new` are removed from the class, so objects without data cannot be
+// created from Ruby.
+pub fn define_copy_functions(
+    klass: Value,
+    allocator: extern "C" fn(Value) -> Value,
+    initialize_copy: Callback<AnyObject, AnyObject>,
+) {
+    let current_allocator = unsafe { rb_get_alloc_func(klass) };
+
+    // The functions are already defined for the class or its ancestor
+    if current_allocator.map(|allocator| allocator as usize) == Some(allocator as usize) {
+        return;
+    }
+
+    define_alloc_func(klass, allocator);
+    define_method(klass, "initialize_copy", initialize_copy);
+
+    let singleton_class = singleton_class(klass);
+    let new_id = binding_util::internal_id("new");
+    let has_default_new = unsafe { rb_method_basic_definition_p(singleton_class, new_id) };
+
+    undef_method(singleton_class, "allocate");
+
+    if util::c_int_to_bool(has_default_new) {
+        undef_method(singleton_class, "new");
+    }
+}
+
+pub fn new_wrapped_data<T>(data: T, data_type: &DataType) -> *mut c_void {
+    let wrapped = Box::into_raw(Box::new(WrappedData {
+        header: Header {
+            data_type: data_type,
+            data: ptr::null_mut(),
+        },
+        data: data,
+    }));
+
+    unsafe { (*wrapped).header.data = &mut (*wrapped).data as *mut T as *mut c_void };
+
+    wrapped as *mut c_void
+}
+
+pub unsafe fn wrapped_data<'a, T>(wrapped: *mut c_void) -> &'a mut T {
+    &mut (*(wrapped as *mut WrappedData<T>)).data
+}
+
+pub fn free_wrapped_data<T>(wrapped: *mut c_void) {
+    // Memory is freed when the box goes out of the scope
+    let _ = unsafe { Box::from_raw(wrapped as *mut WrappedData<T>) };
+}
+
+pub fn wrapped_data_size<T>() -> usize {
+    mem::size_of::<WrappedData<T>>()
 }
 
 pub fn get_data<T>(object: Value, wrapper: &DataTypeWrapper<T>) -> &mut T {
     unsafe {
         let data = typed_data::rb_check_typeddata(object, wrapper.data_type());
 
+        if data.is_null() {
+            let exception = binding_util::get_constant("RuntimeError", rb_cObject);
+
+            vm::raise(exception, "Wrapped data is not initialized");
+        }
+
+        let data = upcast_wrapped_data(data, wrapper.data_type());
+
         &mut *(data as *mut T)
     }
 }
 
+pub fn is_typed_data(object: Value, data_type: &DataType) -> bool {
+    let result = unsafe { rb_typeddata_is_kind_of(object, data_type) };
+
+    util::c_int_to_bool(result)
+}
+
+// Data of child types is converted to the requested parent type step by step with functions
+// stored in `data` fields of data types (see `typed_data::upcast_function()`)
+fn upcast_wrapped_data(wrapped: *mut c_void, data_type: &DataType) -> *mut c_void {
+    let header = unsafe { &*(wrapped as *const Header) };
+
+    let mut data = header.data;
+    let mut current = header.data_type;
+
+    while !ptr::eq(current, data_type) {
+        unsafe {
+            let upcast: extern "C" fn(*mut c_void) -> *mut c_void = mem::transmute((*current).data);
+
+            data = upcast(data);
+            current = (*current).parent;
+        }
+    }
+
+    data
+}
+
+pub fn define_alloc_func(klass: Value, func: extern "C" fn(Value) -> Value) {
+    unsafe { rb_define_alloc_func(klass, func) };
+}
+
+pub fn undef_alloc_func(klass: Value) {
+    unsafe { rb_undef_alloc_func(klass) };
+}
+
+pub fn wrap_typed_data(klass: Value, data: *mut c_void, data_type: &DataType) -> Value {
+    unsafe { typed_data::rb_data_typed_object_wrap(klass, data, data_type) }
+}
+
+pub fn check_typed_data(object: Value, data_type: &DataType) -> *mut c_void {
+    unsafe { typed_data::rb_check_typeddata(object, data_type) }
+}
+
+pub fn set_typed_data(object: Value, data: *mut c_void) {
+    let object = object.value as *mut RData;
+
+    unsafe { (*object).data = data };
+}
+
 pub fn is_frozen(object: Value) -> Value {
     unsafe { class::rb_obj_frozen_p(object) }
 }
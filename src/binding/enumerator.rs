@@ -0,0 +1,30 @@
+use std::ptr;
+
+use ruby_sys::util as ruby_sys_util;
+
+use binding::symbol;
+use binding::util as binding_util;
+use types::{c_int, Argc, Value};
+use util;
+
+pub type BlockCallFunction = extern "C" fn(Value, Value, Argc, *const Value) -> Value;
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_enumeratorize(object: Value, method: Value, argc: c_int, argv: *const Value) -> Value;
+}
+
+pub fn enumeratorize(object: Value, method: &str, arguments: Option<Vec<Value>>) -> Value {
+    let (argc, argv) = util::process_arguments(&arguments);
+    let method = symbol::id_to_sym(binding_util::internal_id(method));
+
+    unsafe { rb_enumeratorize(object, method, argc, argv) }
+}
+
+// Creates `Enumerator.new { |yielder| ... }`, the block calls `func` with the yielder and `data`.
+// `data` is kept alive by the block.
+pub fn new_with_block(enumerator_class: Value, func: BlockCallFunction, data: Value) -> Value {
+    let new = binding_util::internal_id("new");
+
+    unsafe { ruby_sys_util::rb_block_call(enumerator_class, new, 0, ptr::null(), func, data) }
+}
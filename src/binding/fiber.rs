@@ -0,0 +1,44 @@
+use types::{c_int, Value};
+use util;
+
+pub type FiberFunction = extern "C" fn(Value, Value, c_int, *const Value, Value) -> Value;
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_fiber_new(func: FiberFunction, callback_arg: Value) -> Value;
+    fn rb_fiber_current() -> Value;
+    fn rb_fiber_resume(fiber: Value, argc: c_int, argv: *const Value) -> Value;
+    fn rb_fiber_yield(argc: c_int, argv: *const Value) -> Value;
+    fn rb_fiber_alive_p(fiber: Value) -> Value;
+    fn rb_obj_is_fiber(object: Value) -> Value;
+}
+
+// Creates a fiber which calls `func` with the first argument of `resume()` and `data`.
+// `data` is kept alive by the block of the fiber.
+pub fn new(func: FiberFunction, data: Value) -> Value {
+    unsafe { rb_fiber_new(func, data) }
+}
+
+pub fn current() -> Value {
+    unsafe { rb_fiber_current() }
+}
+
+pub fn resume(fiber: Value, arguments: Option<Vec<Value>>) -> Value {
+    let (argc, argv) = util::process_arguments(&arguments);
+
+    unsafe { rb_fiber_resume(fiber, argc, argv) }
+}
+
+pub fn fiber_yield(arguments: Option<Vec<Value>>) -> Value {
+    let (argc, argv) = util::process_arguments(&arguments);
+
+    unsafe { rb_fiber_yield(argc, argv) }
+}
+
+pub fn is_alive(fiber: Value) -> bool {
+    unsafe { rb_fiber_alive_p(fiber).is_true() }
+}
+
+pub fn is_fiber(object: Value) -> bool {
+    unsafe { rb_obj_is_fiber(object).is_true() }
+}
@@ -1,6 +1,25 @@
 use ruby_sys::fixnum;
 
-use types::{SignedValue, Value};
+use types::{c_int, c_void, size_t, SignedValue, Value};
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_ll2inum(num: i64) -> Value;
+    fn rb_ull2inum(num: u64) -> Value;
+    fn rb_integer_pack(
+        num: Value,
+        words: *mut c_void,
+        numwords: size_t,
+        wordsize: size_t,
+        nails: size_t,
+        flags: c_int,
+    ) -> c_int;
+    fn rb_fix2str(num: Value, base: c_int) -> Value;
+    fn rb_big2str(num: Value, base: c_int) -> Value;
+}
+
+const INTEGER_PACK_LSWORD_FIRST: c_int = 0x02;
+const INTEGER_PACK_NATIVE: c_int = 0x40;
 
 pub fn int_to_num(num: i64) -> Value {
     unsafe { fixnum::rb_int2inum(num as SignedValue) }
@@ -9,3 +28,30 @@ pub fn int_to_num(num: i64) -> Value {
 pub fn num_to_int(num: Value) -> i64 {
     unsafe { fixnum::rb_num2int(num) as i64 }
 }
+
+pub fn i64_to_num(num: i64) -> Value {
+    unsafe { rb_ll2inum(num) }
+}
+
+pub fn u64_to_num(num: u64) -> Value {
+    unsafe { rb_ull2inum(num) }
+}
+
+// Returns the absolute value of an integer and its sign (-1, 0 or 1). The sign is -2 or 2 if
+// the absolute value does not fit into 64 bits.
+pub fn integer_to_abs_u64(num: Value) -> (u64, i32) {
+    let mut abs: u64 = 0;
+    let flags = INTEGER_PACK_LSWORD_FIRST | INTEGER_PACK_NATIVE;
+
+    let sign = unsafe { rb_integer_pack(num, &mut abs as *mut u64 as *mut c_void, 1, 8, 0, flags) };
+
+    (abs, sign as i32)
+}
+
+pub fn fixnum_to_string(num: Value) -> Value {
+    unsafe { rb_fix2str(num, 10) }
+}
+
+pub fn bignum_to_string(num: Value) -> Value {
+    unsafe { rb_big2str(num, 10) }
+}
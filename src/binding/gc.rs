@@ -1,7 +1,89 @@
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::sync::OnceLock;
+
 use ruby_sys::gc;
 
-use types::Value;
+#[cfg(unix)]
+use binding::util as binding_util;
+use types::{size_t, Value};
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_gc_count() -> size_t;
+    fn rb_gc_disable() -> Value;
+    fn rb_gc_enable() -> Value;
+    fn rb_gc_mark_locations(start: *const Value, end: *const Value);
+    fn rb_gc_mark_maybe(value: Value);
+    fn rb_gc_register_address(address: *const Value);
+    fn rb_gc_start() -> Value;
+    fn rb_gc_stat(key_or_hash: Value) -> size_t;
+    fn rb_gc_unregister_address(address: *const Value);
+}
 
 pub fn mark(value: Value) {
     unsafe { gc::rb_gc_mark(value) };
 }
+
+pub fn mark_maybe(value: Value) {
+    unsafe { rb_gc_mark_maybe(value) };
+}
+
+pub fn mark_locations(values: &[Value]) {
+    let start = values.as_ptr();
+
+    unsafe { rb_gc_mark_locations(start, start.offset(values.len() as isize)) };
+}
+
+pub unsafe fn register(address: *const Value) {
+    rb_gc_register_address(address);
+}
+
+pub unsafe fn unregister(address: *const Value) {
+    rb_gc_unregister_address(address);
+}
+
+pub fn start() {
+    unsafe { rb_gc_start() };
+}
+
+pub fn enable() -> bool {
+    let was_disabled = unsafe { rb_gc_enable() };
+
+    was_disabled.is_true()
+}
+
+pub fn disable() -> bool {
+    let was_disabled = unsafe { rb_gc_disable() };
+
+    was_disabled.is_true()
+}
+
+pub fn count() -> usize {
+    unsafe { rb_gc_count() as usize }
+}
+
+pub fn stat(key_or_hash: Value) -> usize {
+    unsafe { rb_gc_stat(key_or_hash) as usize }
+}
+
+// `rb_gc_adjust_memory_usage()` is available only since Ruby 2.4, so it is looked up at runtime
+// instead of being linked. The result of the lookup is cached. Nothing is done for older versions
+// of Ruby.
+#[cfg(unix)]
+pub fn adjust_memory_usage(diff: isize) {
+    static FUNCTION: OnceLock<Option<extern "C" fn(isize)>> = OnceLock::new();
+
+    let function = FUNCTION.get_or_init(|| unsafe {
+        mem::transmute(binding_util::find_function("rb_gc_adjust_memory_usage"))
+    });
+
+    if let Some(function) = *function {
+        function(diff);
+    }
+}
+
+// `dlsym()` is not available, so nothing is done on non-Unix systems
+#[cfg(not(unix))]
+pub fn adjust_memory_usage(_diff: isize) {}
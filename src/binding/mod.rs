@@ -1,5 +1,7 @@
 pub mod array;
 pub mod class;
+pub mod enumerator;
+pub mod fiber;
 pub mod fixnum;
 pub mod float;
 pub mod gc;
@@ -10,6 +10,13 @@ pub fn new(string: &str) -> Value {
     unsafe { string::rb_str_new(str, len) }
 }
 
+pub fn new_from_bytes(bytes: &[u8]) -> Value {
+    let str = bytes.as_ptr() as *const c_char;
+    let len = bytes.len() as c_long;
+
+    unsafe { string::rb_str_new(str, len) }
+}
+
 pub fn new_utf8(string: &str) -> Value {
     let str = string.as_ptr() as *const c_char;
     let len = string.len() as c_long;
@@ -55,6 +62,15 @@ pub fn value_to_str_unchecked<'a>(value: Value) -> &'a str {
     }
 }
 
+pub fn value_to_bytes_unchecked<'a>(value: Value) -> &'a [u8] {
+    unsafe {
+        let str = string::rb_string_value_ptr(&value) as *const u8;
+        let len = string::rb_str_len(value) as usize;
+
+        ::std::slice::from_raw_parts(str, len)
+    }
+}
+
 pub fn bytesize(value: Value) -> i64 {
     unsafe { string::rb_str_len(value) as i64 }
 }
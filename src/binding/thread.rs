@@ -1,25 +1,86 @@
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use ruby_sys::thread;
 
-use types::{c_void, CallbackPtr, Value};
+use binding::util as binding_util;
+use types::{c_int, c_void, CallbackPtr, Id, Value};
 use util;
 
+#[cfg(unix)]
+use std::time::Duration;
+
+#[cfg(unix)]
+use libc;
 #[cfg(unix)]
 use types::RawFd;
 
-use Object;
+// Events of `rb_wait_for_single_fd()`
+#[cfg(unix)]
+pub const WAITFD_IN: c_int = 0x001;
+#[cfg(unix)]
+pub const WAITFD_PRI: c_int = 0x002;
+#[cfg(unix)]
+pub const WAITFD_OUT: c_int = 0x004;
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_thread_current() -> Value;
+    fn rb_thread_check_ints();
+    fn ruby_thread_has_gvl_p() -> c_int;
+    fn rb_thread_wakeup(thread: Value) -> Value;
+    fn rb_thread_kill(thread: Value) -> Value;
+    fn rb_thread_local_aref(thread: Value, id: Id) -> Value;
+    fn rb_thread_local_aset(thread: Value, id: Id, value: Value) -> Value;
+    #[cfg(unix)]
+    fn rb_wait_for_single_fd(fd: RawFd, events: c_int, timeout: *mut libc::timeval) -> c_int;
+    fn rb_mutex_new() -> Value;
+    fn rb_mutex_locked_p(mutex: Value) -> Value;
+    fn rb_mutex_trylock(mutex: Value) -> Value;
+    fn rb_mutex_lock(mutex: Value) -> Value;
+    fn rb_mutex_unlock(mutex: Value) -> Value;
+    fn rb_mutex_synchronize(mutex: Value, func: CallbackPtr, arg: CallbackPtr) -> *mut c_void;
+}
 
-pub fn create<F, R>(func: F) -> Value
+pub fn create<F>(func: F) -> Value
 where
-    F: 'static + FnOnce() -> R,
-    R: Object,
+    F: 'static + FnOnce() -> Value,
 {
-    let fnbox = Box::new(func) as Box<FnOnce() -> R>;
+    let fnbox = Box::new(func) as Box<FnOnce() -> Value>;
 
     let closure_ptr = Box::into_raw(Box::new(fnbox)) as *mut c_void;
 
-    unsafe { thread::rb_thread_create(thread_create_callbox::<R>, closure_ptr) }
+    unsafe { thread::rb_thread_create(thread_create_callbox, closure_ptr) }
+}
+
+pub fn current() -> Value {
+    unsafe { rb_thread_current() }
+}
+
+pub fn has_gvl() -> bool {
+    let result = unsafe { ruby_thread_has_gvl_p() };
+
+    util::c_int_to_bool(result)
+}
+
+pub fn check_ints() {
+    unsafe { rb_thread_check_ints() }
+}
+
+pub fn wakeup(thread: Value) -> Value {
+    unsafe { rb_thread_wakeup(thread) }
+}
+
+pub fn kill(thread: Value) -> Value {
+    unsafe { rb_thread_kill(thread) }
+}
+
+pub fn local_aref(thread: Value, name: &str) -> Value {
+    unsafe { rb_thread_local_aref(thread, binding_util::internal_id(name)) }
+}
+
+pub fn local_aset(thread: Value, name: &str, value: Value) -> Value {
+    unsafe { rb_thread_local_aset(thread, binding_util::internal_id(name), value) }
 }
 
 #[cfg(unix)]
@@ -27,6 +88,57 @@ pub fn wait_fd(fd: RawFd) {
     unsafe { thread::rb_thread_wait_fd(fd) };
 }
 
+// Returns fired events, `0` if the timeout expired or `-1` if an error occurred (see `errno`)
+#[cfg(unix)]
+pub fn wait_for_single_fd(fd: RawFd, events: c_int, timeout: Option<Duration>) -> c_int {
+    let mut timeval = timeout.map(|timeout| libc::timeval {
+        tv_sec: timeout.as_secs() as libc::time_t,
+        tv_usec: timeout.subsec_micros() as libc::suseconds_t,
+    });
+
+    let timeval_ptr = match timeval {
+        Some(ref mut timeval) => timeval as *mut libc::timeval,
+        None => ptr::null_mut(),
+    };
+
+    unsafe { rb_wait_for_single_fd(fd, events, timeval_ptr) }
+}
+
+pub fn mutex_new() -> Value {
+    unsafe { rb_mutex_new() }
+}
+
+pub fn mutex_is_locked(mutex: Value) -> bool {
+    unsafe { rb_mutex_locked_p(mutex).is_true() }
+}
+
+pub fn mutex_try_lock(mutex: Value) -> bool {
+    unsafe { rb_mutex_trylock(mutex).is_true() }
+}
+
+pub fn mutex_lock(mutex: Value) {
+    unsafe { rb_mutex_lock(mutex) };
+}
+
+pub fn mutex_unlock(mutex: Value) {
+    unsafe { rb_mutex_unlock(mutex) };
+}
+
+pub fn mutex_synchronize<F, R>(mutex: Value, func: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        let ptr = rb_mutex_synchronize(
+            mutex,
+            thread_call_callbox as CallbackPtr,
+            util::closure_to_ptr(func),
+        );
+
+        util::ptr_to_data(ptr)
+    }
+}
+
 pub fn call_without_gvl<F, R, G>(func: F, unblock_func: Option<G>) -> R
 where
     F: 'static + FnOnce() -> R,
@@ -79,6 +191,24 @@ where
     }
 }
 
+// Unlike `call_without_gvl()`, the unblocking function only sets the `cancelled` flag, so it is
+// safe to call it several times (Ruby does it while the thread is interrupted)
+pub fn call_without_gvl_cancellable<F, R>(func: F, cancelled: &AtomicBool) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        let ptr = thread::rb_thread_call_without_gvl(
+            thread_call_callbox as CallbackPtr,
+            util::closure_to_ptr(func),
+            cancel_callbox as CallbackPtr,
+            cancelled as *const AtomicBool as *const c_void,
+        );
+
+        util::ptr_to_data(ptr)
+    }
+}
+
 pub fn call_with_gvl<F, R>(func: F) -> R
 where
     F: 'static + FnOnce() -> R,
@@ -93,14 +223,11 @@ where
     }
 }
 
-extern "C" fn thread_create_callbox<R>(boxptr: *mut c_void) -> Value
-where
-    R: Object,
-{
-    let mut fnbox: Box<Box<FnMut() -> R>> =
-        unsafe { Box::from_raw(boxptr as *mut Box<FnMut() -> R>) };
+extern "C" fn thread_create_callbox(boxptr: *mut c_void) -> Value {
+    let fnbox: Box<Box<FnOnce() -> Value>> =
+        unsafe { Box::from_raw(boxptr as *mut Box<FnOnce() -> Value>) };
 
-    fnbox().value()
+    fnbox()
 }
 
 extern "C" fn thread_call_callbox(boxptr: *mut c_void) -> *const c_void {
@@ -109,3 +236,11 @@ extern "C" fn thread_call_callbox(boxptr: *mut c_void) -> *const c_void {
 
     fnbox()
 }
+
+extern "C" fn cancel_callbox(cancelled: *mut c_void) -> *const c_void {
+    let cancelled = unsafe { &*(cancelled as *const AtomicBool) };
+
+    cancelled.store(true, Ordering::SeqCst);
+
+    ptr::null()
+}
@@ -1,8 +1,28 @@
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::sync::OnceLock;
+
+#[cfg(unix)]
+use libc;
 use ruby_sys::util as ruby_sys_util;
 
-use types::{Id, Value};
+use binding::global::rb_cObject;
+use binding::vm;
+#[cfg(unix)]
+use types::c_void;
+use types::{c_int, Argc, Id, Value};
 use util;
 
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_funcallv_public(receiver: Value, method: Id, argc: Argc, argv: *const Value) -> Value;
+
+    static ruby_api_version: [c_int; 3];
+}
+
+type FuncallvKw = extern "C" fn(Value, Id, Argc, *const Value, c_int) -> Value;
+
 pub fn get_constant(name: &str, parent_object: Value) -> Value {
     let constant_id = internal_id(name);
 
@@ -16,9 +36,79 @@ pub fn internal_id(string: &str) -> Id {
 }
 
 pub fn call_method(receiver: Value, method: &str, arguments: Option<Vec<Value>>) -> Value {
-    let (argc, argv) = util::process_arguments(&arguments);
-    let method_id = internal_id(method);
+    call_method_with_id(receiver, internal_id(method), &arguments)
+}
+
+// Does not allocate, so it can be called inside `protect`
+pub fn call_method_with_id(
+    receiver: Value,
+    method_id: Id,
+    arguments: &Option<Vec<Value>>,
+) -> Value {
+    let (argc, argv) = util::process_arguments(arguments);
 
     // TODO: Update the signature of `rb_funcallv` in ruby-sys to receive an `Option`
     unsafe { ruby_sys_util::rb_funcallv(receiver, method_id, argc, argv) }
 }
+
+pub fn call_public_method(receiver: Value, method: &str, arguments: Option<Vec<Value>>) -> Value {
+    let (argc, argv) = util::process_arguments(&arguments);
+    let method_id = internal_id(method);
+
+    unsafe { rb_funcallv_public(receiver, method_id, argc, argv) }
+}
+
+// Keywords are passed as the last argument.
+//
+// `rb_funcallv_kw()` is available only since Ruby 2.7, so it is looked up at runtime instead of
+// being linked. Older versions of Ruby receive keywords as a trailing hash, which is treated as
+// keyword arguments by them. Newer versions would treat the hash as a positional argument, so
+// `NotImplementedError` is raised if the function cannot be found (e.g. on non-Unix systems).
+pub fn call_method_kw(receiver: Value, method: &str, arguments: Vec<Value>) -> Value {
+    match funcallv_kw() {
+        Some(function) => {
+            let (argc, argv) = (arguments.len() as Argc, arguments.as_ptr());
+
+            function(receiver, internal_id(method), argc, argv, 1)
+        }
+        None if has_keyword_arguments() => {
+            let exception = unsafe { get_constant("NotImplementedError", rb_cObject) };
+
+            drop(arguments);
+            vm::raise(exception, "rb_funcallv_kw() is not available");
+
+            unreachable!()
+        }
+        None => call_method(receiver, method, Some(arguments)),
+    }
+}
+
+// The result of the lookup is cached, because methods are called often
+#[cfg(unix)]
+fn funcallv_kw() -> Option<FuncallvKw> {
+    static FUNCTION: OnceLock<Option<FuncallvKw>> = OnceLock::new();
+
+    *FUNCTION.get_or_init(|| unsafe { mem::transmute(find_function("rb_funcallv_kw")) })
+}
+
+#[cfg(not(unix))]
+fn funcallv_kw() -> Option<FuncallvKw> {
+    None
+}
+
+// Keyword arguments are separated from positional ones since Ruby 2.7
+fn has_keyword_arguments() -> bool {
+    let version = unsafe { ruby_api_version };
+
+    (version[0], version[1]) >= (2, 7)
+}
+
+// Looks up a function of the Ruby library at runtime. It is used for functions which are not
+// available in all supported versions of Ruby, so they cannot be linked. Returns `NULL` if the
+// function is not found.
+#[cfg(unix)]
+pub fn find_function(name: &str) -> *mut c_void {
+    let name = util::str_to_cstring(name);
+
+    unsafe { libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr()) }
+}
@@ -1,10 +1,41 @@
+#[cfg(unix)]
+use std::mem;
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use ruby_sys::{thread, vm};
 
-use types::{c_int, c_void, CallbackPtr, Value};
+use binding::global::RubySpecialConsts;
+#[cfg(unix)]
+use binding::util as binding_util;
+use class::traits::typed_data;
+use types::{
+    c_char, c_int, c_void, CallbackPtr, InternalValue, Value, VariableGetter, VariableSetter,
+};
 use util;
 
+use Object;
+
+// TODO: Move to ruby-sys
+extern "C" {
+    fn rb_call_super(argc: c_int, argv: *const Value) -> Value;
+    fn rb_errinfo() -> Value;
+    fn rb_set_errinfo(error: Value);
+    fn rb_jump_tag(state: c_int) -> !;
+    fn rb_exc_raise(exception: Value) -> !;
+    fn rb_eval_string(code: *const c_char) -> Value;
+    fn rb_eval_string_protect(code: *const c_char, state: *mut c_int) -> Value;
+    fn ruby_sysinit(argc: *mut c_int, argv: *mut *mut *mut c_char);
+    fn ruby_init_stack(address: *mut Value);
+    fn ruby_options(argc: c_int, argv: *mut *mut c_char) -> *mut c_void;
+    fn ruby_script(name: *const c_char);
+    fn ruby_run_node(node: *mut c_void) -> c_int;
+    fn ruby_cleanup(state: c_int) -> c_int;
+    fn rb_gv_get(name: *const c_char) -> Value;
+    fn rb_gv_set(name: *const c_char, value: Value) -> Value;
+    fn rb_define_virtual_variable(name: *const c_char, getter: CallbackPtr, setter: CallbackPtr);
+}
+
 pub fn block_proc() -> Value {
     unsafe { vm::rb_block_proc() }
 }
@@ -15,12 +46,72 @@ pub fn is_block_given() -> bool {
     util::c_int_to_bool(result)
 }
 
+// `run()` finalizes the VM, so it cannot be called after the VM is initialized
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
 pub fn init() {
+    INITIALIZED.store(true, Ordering::SeqCst);
+
     unsafe {
         vm::ruby_init();
     }
 }
 
+// Works like `main()` of the `ruby` executable. `arguments` are the command line arguments
+// including the program name. `setup` is called after the VM is initialized and before the
+// options are processed.
+//
+// Returns the exit status after `at_exit` handlers are called and the VM is finalized, or `None`
+// if the VM has already been initialized.
+pub fn run<F: FnOnce()>(
+    arguments: &[String],
+    script_name: Option<&str>,
+    setup: F,
+) -> Option<c_int> {
+    if INITIALIZED.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+
+    // The strings must live until the VM is finalized, because Ruby keeps pointers to them
+    let arguments: Vec<_> = arguments
+        .iter()
+        .map(|argument| util::str_to_cstring(argument))
+        .collect();
+
+    let mut argv: Vec<*mut c_char> = arguments
+        .iter()
+        .map(|argument| argument.as_ptr() as *mut c_char)
+        .collect();
+
+    argv.push(ptr::null_mut());
+
+    let mut argc = arguments.len() as c_int;
+    let mut argv = argv.as_mut_ptr();
+    let mut stack_start = Value::from(0);
+
+    unsafe {
+        ruby_sysinit(&mut argc, &mut argv);
+        ruby_init_stack(&mut stack_start);
+        vm::ruby_init();
+
+        // Exceptions raised by `setup` are reported by `ruby_cleanup()` like uncaught
+        // exceptions of a script
+        if let Err(state) = protect(setup) {
+            return Some(ruby_cleanup(state));
+        }
+
+        let node = ruby_options(argc, argv);
+
+        if let Some(script_name) = script_name {
+            let script_name = util::str_to_cstring(script_name);
+
+            ruby_script(script_name.as_ptr());
+        }
+
+        Some(ruby_run_node(node))
+    }
+}
+
 pub fn require(name: &str) {
     let name = util::str_to_cstring(name);
 
@@ -37,6 +128,98 @@ pub fn raise(exception: Value, message: &str) {
     }
 }
 
+// Unlike `raise()`, nothing is allocated on the Rust side, so no memory leaks when Ruby
+// longjmps out of the caller
+pub fn raise_exception(exception: Value) -> ! {
+    unsafe { rb_exc_raise(exception) }
+}
+
+pub fn call_super(arguments: &[Value]) -> Value {
+    unsafe { rb_call_super(arguments.len() as c_int, arguments.as_ptr()) }
+}
+
+// Continues unwinding which was interrupted by `protect()`
+pub fn jump_tag(state: c_int) -> ! {
+    unsafe { rb_jump_tag(state) }
+}
+
+// Returns the exception which has been raised in protected code (`$!`) and clears it.
+// Otherwise the exception would be still set after the code is rescued.
+pub fn take_errinfo() -> Value {
+    unsafe {
+        let errinfo = rb_errinfo();
+
+        rb_set_errinfo(Value::from(RubySpecialConsts::Nil as InternalValue));
+
+        errinfo
+    }
+}
+
+pub fn eval_string(code: &str) -> Value {
+    let code = util::str_to_cstring(code);
+
+    unsafe { rb_eval_string(code.as_ptr()) }
+}
+
+pub fn eval_string_protect(code: &str) -> Result<Value, c_int> {
+    let code = util::str_to_cstring(code);
+    let mut state = 0;
+
+    let value = unsafe { rb_eval_string_protect(code.as_ptr(), &mut state as *mut c_int) };
+
+    if state == 0 {
+        Ok(value)
+    } else {
+        Err(state)
+    }
+}
+
+pub fn global_get(name: &str) -> Value {
+    let name = util::str_to_cstring(name);
+
+    unsafe { rb_gv_get(name.as_ptr()) }
+}
+
+pub fn global_set(name: &str, value: Value) -> Value {
+    let name = util::str_to_cstring(name);
+
+    unsafe { rb_gv_set(name.as_ptr(), value) }
+}
+
+pub fn define_virtual_variable<O: Object>(
+    name: &str,
+    getter: VariableGetter<O>,
+    setter: Option<VariableSetter>,
+) {
+    let name = util::str_to_cstring(name);
+
+    // Ruby makes the variable read-only if the setter is `NULL`
+    let setter = match setter {
+        Some(setter) => setter as CallbackPtr,
+        None => ptr::null(),
+    };
+
+    unsafe {
+        rb_define_virtual_variable(name.as_ptr(), getter as CallbackPtr, setter);
+    }
+}
+
+// `rb_ext_ractor_safe()` is available only since Ruby 3.0, so it is looked up at runtime
+// instead of being linked. Nothing is done for older versions of Ruby.
+#[cfg(unix)]
+pub fn ext_ractor_safe(flag: bool) {
+    let function = binding_util::find_function("rb_ext_ractor_safe");
+
+    if !function.is_null() {
+        let function: extern "C" fn(bool) = unsafe { mem::transmute(function) };
+
+        function(flag);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn ext_ractor_safe(_flag: bool) {}
+
 pub fn thread_call_without_gvl<F, R, G>(func: F, unblock_func: Option<G>) -> R
 where
     F: FnOnce() -> R,
@@ -113,6 +296,7 @@ where
     F: FnOnce(),
 {
     let mut state = 0;
+    let borrows_mark = typed_data::borrows_mark();
     let value = unsafe {
         vm::rb_protect(
             callbox as CallbackPtr,
@@ -123,6 +307,9 @@ where
     if state == 0 {
         Ok(value)
     } else {
+        // Guards of borrowed wrapped data have been skipped by the exception
+        typed_data::release_borrows(borrows_mark);
+
         Err(state)
     }
 }
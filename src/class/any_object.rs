@@ -1,4 +1,6 @@
-use types::Value;
+use std::marker::PhantomData;
+
+use types::{NotSend, Value};
 
 use {Object, VerifiedObject};
 
@@ -51,11 +53,15 @@ use {Object, VerifiedObject};
 #[derive(Clone, Debug, PartialEq)]
 pub struct AnyObject {
     value: Value,
+    _marker: NotSend,
 }
 
 impl From<Value> for AnyObject {
     fn from(value: Value) -> Self {
-        AnyObject { value: value }
+        AnyObject {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
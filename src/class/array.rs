@@ -1,9 +1,10 @@
 use std::convert::From;
 use std::default::Default;
 use std::iter::{FromIterator, IntoIterator, Iterator};
+use std::marker::PhantomData;
 
 use binding::array;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {AnyObject, Object, RString, VerifiedObject};
 
@@ -11,6 +12,7 @@ use {AnyObject, Object, RString, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Array {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Array {
@@ -515,7 +517,10 @@ impl Default for Array {
 
 impl From<Value> for Array {
     fn from(value: Value) -> Self {
-        Array { value: value }
+        Array {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
-use types::Value;
+use types::{NotSend, Value};
 use util;
 
 use {Object, VerifiedObject};
@@ -9,6 +10,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Boolean {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Boolean {
@@ -55,7 +57,10 @@ impl Boolean {
 
 impl From<Value> for Boolean {
     fn from(value: Value) -> Self {
-        Boolean { value: value }
+        Boolean {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -1,13 +1,15 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::class;
 use binding::global::rb_cObject;
 use binding::util as binding_util;
-use typed_data::DataTypeWrapper;
-use types::{Value, ValueType};
+use class::traits::typed_data;
+use typed_data::{CloneableDataTypeWrapper, DataTypeWrapper, MarshalDataTypeWrapper};
+use types::{Callback, NotSend, Value, ValueType};
 use util;
 
-use {AnyObject, Array, Object, VerifiedObject};
+use {AnyObject, Array, MarshalData, Object, Symbol, TypedData, VerifiedObject};
 
 /// `Class`
 ///
@@ -59,6 +61,7 @@ use {AnyObject, Array, Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Class {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Class {
@@ -480,8 +483,272 @@ impl Class {
         class::define_attribute(self.value(), name, true, true);
     }
 
+    /// Defines a private instance method for the class.
+    ///
+    /// Private methods can be called only without an explicit receiver from other methods of
+    /// the object. Use `methods!` macro to define a `callback`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, VM};
+    ///
+    /// class!(Counter);
+    ///
+    /// methods!(
+    ///     Counter,
+    ///     itself,
+    ///
+    ///     fn counter_next() -> Fixnum {
+    ///         let step = itself.send("step", None).try_convert_to::<Fixnum>().unwrap();
+    ///
+    ///         Fixnum::new(step.to_i64() + 1)
+    ///     }
+    ///
+    ///     fn counter_step() -> Fixnum {
+    ///         Fixnum::new(1)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Counter", None).define(|itself| {
+    ///         itself.def("next", counter_next);
+    ///         itself.define_private_method("step", counter_step);
+    ///     });
+    ///
+    ///     let counter = Class::from_existing("Counter").new_instance(None);
+    ///
+    ///     assert!(counter.respond_to("next"));
+    ///     assert!(!counter.respond_to("step"));
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Counter
+    ///   def next
+    ///     step + 1
+    ///   end
+    ///
+    ///   private
+    ///
+    ///   def step
+    ///     1
+    ///   end
+    /// end
+    /// ```
+    pub fn define_private_method<I: Object, O: Object>(
+        &mut self,
+        name: &str,
+        callback: Callback<I, O>,
+    ) {
+        class::define_private_method(self.value(), name, callback);
+    }
+
+    /// Defines a protected instance method for the class.
+    ///
+    /// Protected methods can be called only from methods of objects of the same class (or its
+    /// subclasses). Use `methods!` macro to define a `callback`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{AnyObject, Boolean, Class, Fixnum, Object, VM};
+    ///
+    /// class!(Account);
+    ///
+    /// methods!(
+    ///     Account,
+    ///     itself,
+    ///
+    ///     fn account_is_richer_than(other: AnyObject) -> Boolean {
+    ///         let own_balance = itself.send("balance", None).try_convert_to::<Fixnum>();
+    ///         let other_balance = other.unwrap().send("balance", None).try_convert_to::<Fixnum>();
+    ///
+    ///         Boolean::new(own_balance.unwrap().to_i64() > other_balance.unwrap().to_i64())
+    ///     }
+    ///
+    ///     fn account_balance() -> Fixnum {
+    ///         Fixnum::new(100)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Account", None).define(|itself| {
+    ///         itself.def("richer_than?", account_is_richer_than);
+    ///         itself.define_protected_method("balance", account_balance);
+    ///     });
+    ///
+    ///     let account = Class::from_existing("Account").new_instance(None);
+    ///
+    ///     assert!(!account.respond_to("balance"));
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Account
+    ///   def richer_than?(other)
+    ///     balance > other.balance
+    ///   end
+    ///
+    ///   protected
+    ///
+    ///   def balance
+    ///     100
+    ///   end
+    /// end
+    /// ```
+    pub fn define_protected_method<I: Object, O: Object>(
+        &mut self,
+        name: &str,
+        callback: Callback<I, O>,
+    ) {
+        class::define_protected_method(self.value(), name, callback);
+    }
+
+    /// Makes `new_name` a copy of the existing method `old_name`.
+    ///
+    /// The copy is not changed if the original method is redefined later. Raises `NameError` if
+    /// the method does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// Class::new("Article", None).define(|itself| {
+    ///     itself.attr_reader("title");
+    ///     itself.alias_method("name", "title");
+    /// });
+    ///
+    /// let article = Class::from_existing("Article").new_instance(None);
+    ///
+    /// assert!(article.respond_to("name"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Article
+    ///   attr_reader :title
+    ///   alias_method :name, :title
+    /// end
+    /// ```
+    pub fn alias_method(&mut self, new_name: &str, old_name: &str) {
+        class::define_alias(self.value(), new_name, old_name);
+    }
+
+    /// Prevents objects of the class from responding to the method, including methods
+    /// defined in superclasses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// Class::new("Immutable", None).define(|itself| {
+    ///     itself.undef_method("instance_variable_set");
+    /// });
+    ///
+    /// let object = Class::from_existing("Immutable").new_instance(None);
+    ///
+    /// assert!(!object.respond_to("instance_variable_set"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Immutable
+    ///   undef_method :instance_variable_set
+    /// end
+    /// ```
+    pub fn undef_method(&mut self, name: &str) {
+        class::undef_method(self.value(), name);
+    }
+
+    /// Removes the method defined in the class, so the method of a superclass is called
+    /// instead.
+    ///
+    /// Raises `NameError` if the method is not defined in the class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// VM::eval("class Greeting; def to_s; 'Hello'; end; end");
+    ///
+    /// Class::from_existing("Greeting").remove_method("to_s");
+    ///
+    /// let greeting = Class::from_existing("Greeting").new_instance(None);
+    /// let string = greeting.send("to_s", None).try_convert_to::<RString>().unwrap();
+    ///
+    /// assert!(string.to_str().starts_with("#<Greeting"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Greeting
+    ///   def to_s
+    ///     'Hello'
+    ///   end
+    ///
+    ///   remove_method :to_s
+    /// end
+    /// ```
+    pub fn remove_method(&mut self, name: &str) {
+        class::remove_method(self.value(), name);
+    }
+
+    /// Makes the constant of the class private, so it cannot be accessed with an explicit
+    /// scope (`Class::CONSTANT`) outside of the class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// Class::new("Connection", None).define(|itself| {
+    ///     itself.const_set("TIMEOUT", &Fixnum::new(10));
+    ///     itself.private_constant("TIMEOUT");
+    /// });
+    ///
+    /// assert!(VM::eval_protected("Connection::TIMEOUT").is_err());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Connection
+    ///   TIMEOUT = 10
+    ///   private_constant :TIMEOUT
+    /// end
+    /// ```
+    pub fn private_constant(&mut self, name: &str) {
+        let name = Symbol::new(name).to_any_object();
+
+        self.send("private_constant", Some(&[name]));
+    }
+
     /// Wraps Rust structure into a new Ruby object of the current class.
     ///
+    /// Raises `TypeError` if the wrapper is created with the `class` option and the current
+    /// class is neither that class nor its subclass.
+    ///
     /// See the documentation for `wrappable_struct!` macro for more information.
     ///
     /// # Examples
@@ -490,7 +757,6 @@ impl Class {
     ///
     /// ```
     /// #[macro_use] extern crate ruru;
-    /// #[macro_use] extern crate lazy_static;
     ///
     /// use ruru::{AnyObject, Class, Fixnum, Object, RString, VM};
     ///
@@ -517,7 +783,7 @@ impl Class {
     ///     }
     /// }
     ///
-    /// wrappable_struct!(Server, ServerWrapper, SERVER_WRAPPER);
+    /// wrappable_struct!(Server, ServerWrapper, SERVER_WRAPPER, class("RubyServer"));
     ///
     /// class!(RubyServer);
     ///
@@ -529,17 +795,17 @@ impl Class {
     ///         let server = Server::new(host.unwrap().to_string(),
     ///                                  port.unwrap().to_i64() as u16);
     ///
-    ///         Class::from_existing("RubyServer").wrap_data(server, &*SERVER_WRAPPER)
+    ///         Class::from_existing("RubyServer").wrap_data(server, &SERVER_WRAPPER)
     ///     }
     ///
     ///     fn ruby_server_host() -> RString {
-    ///         let host = itself.get_data(&*SERVER_WRAPPER).host();
+    ///         let host = itself.get_data(&SERVER_WRAPPER).host();
     ///
     ///         RString::new(host)
     ///     }
     ///
     ///     fn ruby_server_port() -> Fixnum {
-    ///         let port = itself.get_data(&*SERVER_WRAPPER).port();
+    ///         let port = itself.get_data(&SERVER_WRAPPER).port();
     ///
     ///         Fixnum::new(port as i64)
     ///     }
@@ -572,6 +838,196 @@ impl Class {
         O::from(value)
     }
 
+    /// Defines an allocation function for the class which wraps `T::default()`.
+    ///
+    /// It allows to create instances of the class with `Class#new` and `Class#allocate` and
+    /// to set up the wrapped data in `initialize` using `Object::with_borrow_mut()`.
+    ///
+    /// See `TypedData` for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, TypedData, VM};
+    ///
+    /// #[derive(Default)]
+    /// pub struct Buffer {
+    ///     bytes: Vec<u8>,
+    /// }
+    ///
+    /// impl TypedData for Buffer {
+    ///     fn class() -> Class {
+    ///         Class::from_existing("Buffer")
+    ///     }
+    ///
+    ///     typed_data_type!(Buffer);
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Buffer", None).define_allocator::<Buffer>();
+    ///
+    ///     let buffer = Class::from_existing("Buffer").new_instance(None);
+    ///
+    ///     assert!(buffer.with_borrow(|buffer: &Buffer| buffer.bytes.is_empty()));
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// buffer = Buffer.new
+    /// ```
+    pub fn define_allocator<T: TypedData + Default>(&mut self) {
+        class::define_alloc_func(self.value(), typed_data::allocate::<T>);
+    }
+
+    /// Removes the allocator of the class.
+    ///
+    /// After that, instances of the class cannot be created with `Class#new` and
+    /// `Class#allocate`. It is useful for classes of objects with wrapped data, which are
+    /// created only from Rust, because the default allocator creates objects without any data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, VM};
+    /// # VM::init();
+    ///
+    /// Class::new("Connection", None).undef_allocator();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// Connection.new # raises TypeError: allocator undefined for Connection
+    /// ```
+    pub fn undef_allocator(&mut self) {
+        class::undef_alloc_func(self.value());
+    }
+
+    /// Makes `dup` and `clone` of objects with wrapped data copy the data.
+    ///
+    /// The wrapper must be created by `wrappable_struct!` with the `clone` option, which requires
+    /// the struct to implement `Clone`. `wrap_data()` calls this function automatically for such
+    /// wrappers, so it is needed only to make the class copyable before any data is wrapped.
+    /// Without the `clone` option, `dup` and `clone` of the objects raise `TypeError`.
+    ///
+    /// The copy gets its own clone of the data, so changes to the data of the copy do not
+    /// affect the original object and vice versa.
+    ///
+    /// Copies are created by the allocator of the class without data, so the function removes
+    /// `allocate` and the default `new` (unless the class defines its own `new`) from the class.
+    /// Objects without data could not be used, because `get_data()` raises an exception for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{AnyObject, Class, Object, VM};
+    ///
+    /// #[derive(Clone)]
+    /// pub struct Counter {
+    ///     count: u32,
+    /// }
+    ///
+    /// wrappable_struct!(Counter, CounterWrapper, COUNTER_WRAPPER, clone);
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     let data_class = Class::from_existing("Data");
+    ///     let mut counter_class = Class::new("Counter", Some(&data_class));
+    ///
+    ///     counter_class.define_copy(&COUNTER_WRAPPER);
+    ///
+    ///     // `Counter.allocate` and `Counter.new` are removed
+    ///     assert!(!counter_class.respond_to("allocate"));
+    ///
+    ///     let counter = Counter { count: 1 };
+    ///     let mut counter: AnyObject = counter_class.wrap_data(counter, &COUNTER_WRAPPER);
+    ///     let copy = counter.send("dup", None);
+    ///
+    ///     counter.get_data_mut(&COUNTER_WRAPPER).count = 2;
+    ///
+    ///     assert_eq!(copy.get_data(&COUNTER_WRAPPER).count, 1);
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// # `counter` wraps `Counter { count: 1 }`
+    /// copy = counter.dup
+    /// ```
+    pub fn define_copy<T: Clone>(&mut self, wrapper: &CloneableDataTypeWrapper<T>) {
+        class::define_copy_functions(self.value(), wrapper.allocator(), wrapper.initialize_copy());
+    }
+
+    /// Makes objects with wrapped data dumpable by `Marshal`.
+    ///
+    /// Defines `_dump` method, which dumps the wrapped data with `MarshalData::dump()`,
+    /// and `_load` singleton method, which wraps the data restored by `MarshalData::load()`
+    /// into a new instance of the class. The wrapper must be created by `wrappable_struct!`
+    /// with the `marshal` option.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::result::{Error, Result};
+    /// use ruru::{AnyObject, Class, MarshalData, Object, VM};
+    ///
+    /// pub struct Counter {
+    ///     count: u32,
+    /// }
+    ///
+    /// impl MarshalData for Counter {
+    ///     fn dump(&self) -> Result<Vec<u8>> {
+    ///         Ok(self.count.to_string().into_bytes())
+    ///     }
+    ///
+    ///     fn load(bytes: &[u8]) -> Result<Self> {
+    ///         String::from_utf8_lossy(bytes)
+    ///             .parse()
+    ///             .map(|count| Counter { count: count })
+    ///             .map_err(|_| Error::ArgumentError("Invalid counter".to_string()))
+    ///     }
+    /// }
+    ///
+    /// wrappable_struct!(Counter, CounterWrapper, COUNTER_WRAPPER, marshal);
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     let data_class = Class::from_existing("Data");
+    ///     let mut counter_class = Class::new("Counter", Some(&data_class));
+    ///
+    ///     counter_class.define_marshal(&COUNTER_WRAPPER);
+    ///
+    ///     let counter: AnyObject = counter_class.wrap_data(Counter { count: 5 }, &COUNTER_WRAPPER);
+    ///
+    ///     let marshal = Class::from_existing("Marshal");
+    ///     let dump = marshal.send("dump", Some(&[counter]));
+    ///     let counter = marshal.send("load", Some(&[dump]));
+    ///
+    ///     assert_eq!(counter.get_data(&COUNTER_WRAPPER).count, 5);
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// # `counter` wraps `Counter { count: 5 }`
+    /// counter = Marshal.load(Marshal.dump(counter))
+    /// ```
+    pub fn define_marshal<T: MarshalData>(&mut self, wrapper: &MarshalDataTypeWrapper<T>) {
+        self.define_method("_dump", wrapper.dump());
+        self.define_singleton_method("_load", wrapper.load());
+    }
+
     fn superclass_to_value(superclass: Option<&Class>) -> Value {
         match superclass {
             Some(class) => class.value(),
@@ -582,7 +1038,10 @@ impl Class {
 
 impl From<Value> for Class {
     fn from(value: Value) -> Self {
-        Class { value: value }
+        Class {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
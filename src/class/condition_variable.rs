@@ -0,0 +1,166 @@
+use std::convert::From;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use binding::class;
+use types::{NotSend, Value};
+
+use {Class, Float, Mutex, NilClass, Object, VerifiedObject};
+
+/// `ConditionVariable`
+#[derive(Debug, PartialEq)]
+pub struct ConditionVariable {
+    value: Value,
+    _marker: NotSend,
+}
+
+impl ConditionVariable {
+    /// Creates a new `ConditionVariable`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{ConditionVariable, VM};
+    /// # VM::init();
+    ///
+    /// let condition_variable = ConditionVariable::new();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// condition_variable = ConditionVariable.new
+    /// ```
+    pub fn new() -> Self {
+        let condition_variable = Class::from_existing("ConditionVariable").new_instance(None);
+
+        Self::from(condition_variable.value())
+    }
+
+    /// Releases the mutex and waits until the condition variable is signaled or the timeout
+    /// expires. The mutex is locked again before returning.
+    ///
+    /// The mutex must be locked by the current thread. If `timeout` is `None`, waits without a
+    /// limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use ruru::{ConditionVariable, Gvl, Mutex, Object, SendRooted, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let mutex = Mutex::new();
+    /// let condition_variable = ConditionVariable::new();
+    ///
+    /// let signaling_mutex = SendRooted::new(Mutex::from(mutex.value()));
+    /// let signaling_condition_variable =
+    ///     SendRooted::new(ConditionVariable::from(condition_variable.value()));
+    ///
+    /// mutex.synchronize(|| {
+    ///     Thread::new(move || {
+    ///         let gvl = Gvl::current().unwrap();
+    ///         let signaling_mutex = signaling_mutex.get(&gvl);
+    ///         let signaling_condition_variable = signaling_condition_variable.get(&gvl);
+    ///
+    ///         signaling_mutex.synchronize(|| signaling_condition_variable.signal());
+    ///     });
+    ///
+    ///     condition_variable.wait(&mutex, None);
+    ///
+    ///     // Nobody signals the condition variable anymore
+    ///     condition_variable.wait(&mutex, Some(Duration::from_millis(10)));
+    /// });
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// mutex = Mutex.new
+    /// condition_variable = ConditionVariable.new
+    ///
+    /// mutex.synchronize do
+    ///   Thread.new do
+    ///     mutex.synchronize { condition_variable.signal }
+    ///   end
+    ///
+    ///   condition_variable.wait(mutex)
+    ///
+    ///   condition_variable.wait(mutex, 0.01)
+    /// end
+    /// ```
+    pub fn wait(&self, mutex: &Mutex, timeout: Option<Duration>) {
+        let timeout = match timeout {
+            Some(timeout) => Float::new(timeout.as_secs_f64()).to_any_object(),
+            None => NilClass::new().to_any_object(),
+        };
+
+        self.send("wait", Some(&[mutex.to_any_object(), timeout]));
+    }
+
+    /// Wakes up the first thread waiting for the condition variable.
+    ///
+    /// See `wait()` for examples.
+    pub fn signal(&self) {
+        self.send("signal", None);
+    }
+
+    /// Wakes up all the threads waiting for the condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{ConditionVariable, VM};
+    /// # VM::init();
+    ///
+    /// let condition_variable = ConditionVariable::new();
+    ///
+    /// condition_variable.broadcast();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// condition_variable = ConditionVariable.new
+    ///
+    /// condition_variable.broadcast
+    /// ```
+    pub fn broadcast(&self) {
+        self.send("broadcast", None);
+    }
+}
+
+impl Default for ConditionVariable {
+    fn default() -> Self {
+        ConditionVariable::new()
+    }
+}
+
+impl From<Value> for ConditionVariable {
+    fn from(value: Value) -> Self {
+        ConditionVariable {
+            value: value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Object for ConditionVariable {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for ConditionVariable {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        let condition_variable = Class::from_existing("ConditionVariable");
+
+        class::is_kind_of(object.value(), condition_variable.value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to ConditionVariable"
+    }
+}
@@ -0,0 +1,201 @@
+use binding::vm;
+use result::EmbeddedError;
+
+/// Ruby embedded into a Rust application
+///
+/// `Embedded` configures the Ruby VM, runs a script and finalizes the VM like the `ruby`
+/// executable does. It should be used instead of `VM::init()` when the Rust application runs Ruby
+/// scripts itself, for example, as a plugin engine.
+///
+/// The VM is finalized after the script is run (`at_exit` handlers are called, all the threads
+/// are killed, etc), so it cannot be used anymore and another script cannot be run in the same
+/// process. Running a script returns `EmbeddedError::AlreadyInitialized` if the VM has already
+/// been initialized by `VM::init()` or by another script.
+///
+/// # Examples
+///
+/// ```
+/// use ruru::{Class, Embedded};
+///
+/// let status = Embedded::new()
+///     .script_name("plugin_engine")
+///     .load_path("plugins")
+///     .default_external("UTF-8")
+///     .argument("--verbose")
+///     .setup(|| {
+///         // Define classes which are used by plugins
+///         Class::new("PluginEngine", None);
+///     })
+///     .run_string("exit(ARGV == ['--verbose'] && defined?(PluginEngine) ? 3 : 1)");
+///
+/// assert_eq!(status, Ok(3));
+/// ```
+///
+/// The VM cannot be initialized twice:
+///
+/// ```
+/// use ruru::result::EmbeddedError;
+/// use ruru::{Embedded, VM};
+///
+/// VM::init();
+///
+/// let result = Embedded::new().run_string("puts 'Hello'");
+///
+/// assert_eq!(result, Err(EmbeddedError::AlreadyInitialized));
+/// ```
+///
+/// Command line:
+///
+/// ```bash
+/// ruby -I plugins --external-encoding=UTF-8 -e "exit(...)" -- --verbose
+/// ```
+pub struct Embedded {
+    options: Vec<String>,
+    arguments: Vec<String>,
+    script_name: Option<String>,
+    setup: Option<Box<FnOnce()>>,
+}
+
+impl Embedded {
+    /// Creates a new configuration of the Ruby VM.
+    pub fn new() -> Self {
+        Embedded {
+            options: Vec::new(),
+            arguments: Vec::new(),
+            script_name: None,
+            setup: None,
+        }
+    }
+
+    /// Sets the name of the script (`$0` and `$PROGRAM_NAME`).
+    ///
+    /// By default the name is the path of the script for `run_file()` and `"-e"` for
+    /// `run_string()`.
+    pub fn script_name(mut self, name: &str) -> Self {
+        self.script_name = Some(name.to_string());
+        self
+    }
+
+    /// Adds an argument which is passed to the script in `ARGV`.
+    pub fn argument(mut self, argument: &str) -> Self {
+        self.arguments.push(argument.to_string());
+        self
+    }
+
+    /// Adds a directory to `$LOAD_PATH` (`-I` option of `ruby`).
+    pub fn load_path(mut self, path: &str) -> Self {
+        self.options.push(format!("-I{}", path));
+        self
+    }
+
+    /// Sets `Encoding.default_external` (`--external-encoding` option of `ruby`).
+    pub fn default_external(mut self, encoding: &str) -> Self {
+        let option = format!("--external-encoding={}", encoding);
+
+        self.options.push(option);
+        self
+    }
+
+    /// Sets `Encoding.default_internal` (`--internal-encoding` option of `ruby`).
+    pub fn default_internal(mut self, encoding: &str) -> Self {
+        let option = format!("--internal-encoding={}", encoding);
+
+        self.options.push(option);
+        self
+    }
+
+    /// Sets a function which is called after the VM is initialized and before the script is
+    /// run.
+    ///
+    /// It can be used to define classes and methods which are available to the script. If the
+    /// function raises an exception, the exception is reported and the script is not run.
+    pub fn setup<F: 'static + FnOnce()>(mut self, setup: F) -> Self {
+        self.setup = Some(Box::new(setup));
+        self
+    }
+
+    /// Runs a Ruby script from the file and finalizes the VM.
+    ///
+    /// Returns the exit status of the script. Uncaught exceptions are reported to `STDERR` and
+    /// the exit status is `1` in this case.
+    ///
+    /// Returns `EmbeddedError::AlreadyInitialized` if the VM has already been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::process;
+    ///
+    /// use ruru::Embedded;
+    ///
+    /// let status = Embedded::new().run_file("scripts/main.rb").unwrap();
+    ///
+    /// process::exit(status);
+    /// ```
+    ///
+    /// Command line:
+    ///
+    /// ```bash
+    /// ruby scripts/main.rb
+    /// ```
+    pub fn run_file(self, path: &str) -> Result<i32, EmbeddedError> {
+        self.run(vec!["--".to_string(), path.to_string()])
+    }
+
+    /// Runs a string of Ruby code and finalizes the VM.
+    ///
+    /// Returns the exit status of the code. Uncaught exceptions are reported to `STDERR` and the
+    /// exit status is `1` in this case.
+    ///
+    /// Returns `EmbeddedError::AlreadyInitialized` if the VM has already been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::Embedded;
+    ///
+    /// let status = Embedded::new().run_string("at_exit { exit 2 }; puts 'Hello'");
+    ///
+    /// assert_eq!(status, Ok(2));
+    /// ```
+    ///
+    /// Command line:
+    ///
+    /// ```bash
+    /// ruby -e "at_exit { exit 2 }; puts 'Hello'"
+    /// ```
+    pub fn run_string(self, code: &str) -> Result<i32, EmbeddedError> {
+        self.run(vec!["-e".to_string(), code.to_string(), "--".to_string()])
+    }
+
+    fn run(self, script: Vec<String>) -> Result<i32, EmbeddedError> {
+        let Embedded {
+            options,
+            arguments,
+            script_name,
+            setup,
+        } = self;
+
+        // Arguments follow `--` or the path of the script, so they are not processed as options
+        // of `ruby`
+        let mut command_line = vec!["ruby".to_string()];
+
+        command_line.extend(options);
+        command_line.extend(script);
+        command_line.extend(arguments);
+
+        let status = vm::run(&command_line, script_name.as_deref(), || {
+            if let Some(setup) = setup {
+                setup();
+            }
+        });
+
+        status.ok_or(EmbeddedError::AlreadyInitialized)
+    }
+}
+
+impl Default for Embedded {
+    fn default() -> Self {
+        Embedded::new()
+    }
+}
@@ -0,0 +1,156 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::{class, enumerator};
+use codegen;
+use types::{Argc, NotSend, Value};
+
+use {AnyObject, Class, NilClass, Object, ToRuby, TypedData, VerifiedObject};
+
+/// `Enumerator`
+#[derive(Debug, PartialEq)]
+pub struct Enumerator {
+    value: Value,
+    _marker: NotSend,
+}
+
+impl Enumerator {
+    /// Creates a new lazy enumerator which yields items of the iterator.
+    ///
+    /// Items are produced only when they are requested by Ruby. Each enumeration (`each`,
+    /// `to_a`, `rewind` followed by `next`, etc) starts from the beginning with a new iterator
+    /// created from a clone of `iterator`.
+    ///
+    /// `iterator` must be `Send`, so it cannot hold Ruby objects, which would not be marked by
+    /// GC. Ruby objects can be created by the iterator for each item instead. Panics of the
+    /// iterator are raised as `RuntimeError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Enumerator, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let enumerator = Enumerator::from_iter((1..4).map(Fixnum::new));
+    ///
+    /// let first = enumerator.send("next", None).try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(first, Ok(Fixnum::new(1)));
+    ///
+    /// let all = enumerator.send("to_a", None).try_convert_to::<Array>().unwrap();
+    ///
+    /// assert_eq!(all.length(), 3);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// enumerator = Enumerator.new do |yielder|
+    ///   (1..3).each { |number| yielder << number }
+    /// end
+    ///
+    /// enumerator.next == 1
+    /// enumerator.to_a == [1, 2, 3]
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(iterator: I) -> Self
+    where
+        I: 'static + Send + Clone + IntoIterator,
+        I::Item: ToRuby,
+    {
+        let data = RustIterator {
+            new_iterator: Box::new(move || {
+                Box::new(iterator.clone().into_iter().map(ToRuby::to_ruby))
+            }),
+        };
+
+        let enumerator_class = Class::from_existing("Enumerator");
+        let result =
+            enumerator::new_with_block(enumerator_class.value(), each_item, data.wrap().value());
+
+        Self::from(result)
+    }
+}
+
+// The wrappers are never exposed to Ruby code
+
+struct RustIterator {
+    new_iterator: Box<Fn() -> Box<Iterator<Item = AnyObject>>>,
+}
+
+impl TypedData for RustIterator {
+    fn class() -> Class {
+        Class::from_existing("Object")
+    }
+
+    typed_data_type!(RustIterator);
+}
+
+// The state of an enumeration is wrapped into a Ruby object, so it is freed by GC even if the
+// block breaks the enumeration
+struct Enumeration {
+    iterator: Box<Iterator<Item = AnyObject>>,
+}
+
+impl TypedData for Enumeration {
+    fn class() -> Class {
+        Class::from_existing("Object")
+    }
+
+    typed_data_type!(Enumeration);
+}
+
+extern "C" fn each_item(yielder: Value, data: Value, _argc: Argc, _argv: *const Value) -> Value {
+    let yielder = AnyObject::from(yielder);
+    let data = AnyObject::from(data);
+
+    // Panics cannot unwind into Ruby
+    let result = codegen::method(|| {
+        let iterator = data.with_borrow(|data: &RustIterator| (data.new_iterator)());
+        let enumeration = Enumeration { iterator: iterator }.wrap();
+
+        // The iterator is not borrowed while the item is yielded, because the block can break
+        // the enumeration and the borrow would never be released
+        loop {
+            let item = enumeration
+                .with_borrow_mut(|enumeration: &mut Enumeration| enumeration.iterator.next());
+
+            match item {
+                Some(item) => {
+                    yielder.send("<<", Some(&[item]));
+                }
+                None => return NilClass::new().to_any_object(),
+            }
+        }
+    });
+
+    result.value()
+}
+
+impl From<Value> for Enumerator {
+    fn from(value: Value) -> Self {
+        Enumerator {
+            value: value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Object for Enumerator {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Enumerator {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        let enumerator = Class::from_existing("Enumerator");
+
+        class::is_kind_of(object.value(), enumerator.value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Enumerator"
+    }
+}
@@ -0,0 +1,168 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::class;
+use types::{NotSend, Value};
+
+use {Array, Class, Object, RString, VerifiedObject};
+
+/// `Exception`
+#[derive(Debug, PartialEq)]
+pub struct Exception {
+    value: Value,
+    _marker: NotSend,
+}
+
+impl Exception {
+    /// Creates a new instance of an exception class.
+    ///
+    /// The exception is not raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Exception, VM};
+    /// # VM::init();
+    ///
+    /// let argument_error = Class::from_existing("ArgumentError");
+    /// let exception = Exception::new(&argument_error, Some("Wrong argument"));
+    ///
+    /// assert_eq!(exception.message(), "Wrong argument");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// exception = ArgumentError.new('Wrong argument')
+    ///
+    /// exception.message == 'Wrong argument'
+    /// ```
+    pub fn new(class: &Class, message: Option<&str>) -> Self {
+        let exception = match message {
+            Some(message) => class.new_instance(Some(&[RString::new(message).to_any_object()])),
+            None => class.new_instance(None),
+        };
+
+        Self::from(exception.value())
+    }
+
+    /// Returns the message of the exception (Ruby `Exception#message`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// let exception = VM::eval_protected("raise 'Something went wrong'").unwrap_err();
+    ///
+    /// assert_eq!(exception.message(), "Something went wrong");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// begin
+    ///   raise 'Something went wrong'
+    /// rescue => exception
+    ///   exception.message == 'Something went wrong'
+    /// end
+    /// ```
+    pub fn message(&self) -> String {
+        unsafe { self.send("message", None).to::<RString>().to_string() }
+    }
+
+    /// Returns a human-readable representation of the exception including its class and message
+    /// (Ruby `Exception#inspect`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Exception, VM};
+    /// # VM::init();
+    ///
+    /// let exception = Exception::new(&Class::from_existing("TypeError"), Some("Wrong type"));
+    ///
+    /// assert_eq!(exception.inspect(), "#<TypeError: Wrong type>");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// exception = TypeError.new('Wrong type')
+    ///
+    /// exception.inspect == '#<TypeError: Wrong type>'
+    /// ```
+    pub fn inspect(&self) -> String {
+        unsafe { self.send("inspect", None).to::<RString>().to_string() }
+    }
+
+    /// Returns the backtrace of the exception or `None` if the exception has not been raised
+    /// (Ruby `Exception#backtrace`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Exception, VM};
+    /// # VM::init();
+    ///
+    /// let exception = VM::eval_protected("raise 'Something went wrong'").unwrap_err();
+    ///
+    /// assert!(exception.backtrace().is_some());
+    ///
+    /// let exception = Exception::new(&Class::from_existing("RuntimeError"), None);
+    ///
+    /// assert!(exception.backtrace().is_none());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// begin
+    ///   raise 'Something went wrong'
+    /// rescue => exception
+    ///   !exception.backtrace.nil?
+    /// end
+    ///
+    /// exception = RuntimeError.new
+    ///
+    /// exception.backtrace.nil?
+    /// ```
+    pub fn backtrace(&self) -> Option<Array> {
+        let backtrace = self.send("backtrace", None);
+
+        if backtrace.is_nil() {
+            None
+        } else {
+            Some(unsafe { backtrace.to::<Array>() })
+        }
+    }
+}
+
+impl From<Value> for Exception {
+    fn from(value: Value) -> Self {
+        Exception {
+            value: value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Object for Exception {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Exception {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        let exception = Class::from_existing("Exception");
+
+        class::is_kind_of(object.value(), exception.value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Exception"
+    }
+}
@@ -0,0 +1,231 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::fiber;
+use codegen;
+use types::{c_int, NotSend, Value};
+use util;
+
+use {AnyObject, Class, Object, TypedData, VerifiedObject};
+
+/// `Fiber`
+#[derive(Debug, PartialEq)]
+pub struct Fiber {
+    value: Value,
+    _marker: NotSend,
+}
+
+impl Fiber {
+    /// Creates a new fiber which runs the closure.
+    ///
+    /// The closure is not started until the fiber is resumed for the first time. It receives
+    /// the first argument of the first `resume()` (or `nil`) and its result is returned from the
+    /// last `resume()`.
+    ///
+    /// A panic in the closure is raised as `RuntimeError` in the code which resumed the fiber.
+    /// If the fiber is never resumed, the closure is dropped when the fiber is garbage collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fiber, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let fiber = Fiber::new(|start| {
+    ///     let start = start.try_convert_to::<Fixnum>().unwrap().to_i64();
+    ///
+    ///     for number in start..start + 2 {
+    ///         Fiber::yield_values(Some(&[Fixnum::new(number).to_any_object()]));
+    ///     }
+    ///
+    ///     Fixnum::new(0)
+    /// });
+    ///
+    /// let start = [Fixnum::new(10).to_any_object()];
+    ///
+    /// assert_eq!(fiber.resume(Some(&start)).try_convert_to::<Fixnum>(), Ok(Fixnum::new(10)));
+    /// assert_eq!(fiber.resume(None).try_convert_to::<Fixnum>(), Ok(Fixnum::new(11)));
+    /// assert_eq!(fiber.resume(None).try_convert_to::<Fixnum>(), Ok(Fixnum::new(0)));
+    /// assert!(!fiber.is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// fiber = Fiber.new do |start|
+    ///   (start...start + 2).each do |number|
+    ///     Fiber.yield(number)
+    ///   end
+    ///
+    ///   0
+    /// end
+    ///
+    /// fiber.resume(10) == 10
+    /// fiber.resume == 11
+    /// fiber.resume == 0
+    /// fiber.alive? == false
+    /// ```
+    pub fn new<F, R>(func: F) -> Self
+    where
+        F: 'static + FnOnce(AnyObject) -> R,
+        R: Object,
+    {
+        let data = FiberClosure {
+            func: Some(Box::new(move |argument| func(argument).to_any_object())),
+        };
+
+        let fiber = fiber::new(fiber_callbox, data.wrap().value());
+
+        Self::from(fiber)
+    }
+
+    /// Returns the currently executing fiber.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fiber, VM};
+    /// # VM::init();
+    ///
+    /// assert!(Fiber::current().is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// Fiber.current.alive? == true
+    /// ```
+    pub fn current() -> Self {
+        Self::from(fiber::current())
+    }
+
+    /// Resumes the fiber with the arguments.
+    ///
+    /// The arguments are returned from `Fiber::yield_values()` which suspended the fiber, or
+    /// passed to the closure if the fiber is resumed for the first time. Returns the value
+    /// passed to the next `Fiber::yield_values()` or the result of the closure.
+    ///
+    /// Raises `FiberError` if the fiber is dead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fiber, Fixnum, NilClass, Object, VM};
+    /// # VM::init();
+    ///
+    /// let fiber = Fiber::new(|_| {
+    ///     let number = Fiber::yield_values(None).try_convert_to::<Fixnum>().unwrap();
+    ///
+    ///     Fixnum::new(number.to_i64() * 2)
+    /// });
+    ///
+    /// assert!(fiber.resume(None).is_nil());
+    ///
+    /// let result = fiber.resume(Some(&[Fixnum::new(21).to_any_object()]));
+    ///
+    /// assert_eq!(result.try_convert_to::<Fixnum>(), Ok(Fixnum::new(42)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// fiber = Fiber.new do
+    ///   Fiber.yield * 2
+    /// end
+    ///
+    /// fiber.resume == nil
+    /// fiber.resume(21) == 42
+    /// ```
+    pub fn resume(&self, arguments: Option<&[AnyObject]>) -> AnyObject {
+        let arguments = util::arguments_to_values(arguments);
+        let result = fiber::resume(self.value(), arguments);
+
+        AnyObject::from(result)
+    }
+
+    /// Suspends the current fiber and returns the arguments to the code which resumed it
+    /// (Ruby `Fiber.yield`).
+    ///
+    /// Returns the arguments of the next `resume()`. Several arguments are returned as an
+    /// `Array`, no arguments are returned as `nil`.
+    ///
+    /// Raises `FiberError` if it is called from the root fiber.
+    ///
+    /// See `new()` and `resume()` for examples.
+    pub fn yield_values(arguments: Option<&[AnyObject]>) -> AnyObject {
+        let arguments = util::arguments_to_values(arguments);
+        let result = fiber::fiber_yield(arguments);
+
+        AnyObject::from(result)
+    }
+
+    /// Returns `true` if the fiber can be resumed.
+    ///
+    /// See `new()` for examples.
+    pub fn is_alive(&self) -> bool {
+        fiber::is_alive(self.value())
+    }
+}
+
+// The closure is wrapped into a Ruby object which is kept alive by the fiber, so it is freed by
+// GC if the fiber is never resumed
+struct FiberClosure {
+    func: Option<Box<FnOnce(AnyObject) -> AnyObject>>,
+}
+
+impl TypedData for FiberClosure {
+    fn class() -> Class {
+        Class::from_existing("Object")
+    }
+
+    typed_data_type!(FiberClosure);
+}
+
+extern "C" fn fiber_callbox(
+    argument: Value,
+    data: Value,
+    _argc: c_int,
+    _argv: *const Value,
+    _block: Value,
+) -> Value {
+    let argument = AnyObject::from(argument);
+    let data = AnyObject::from(data);
+
+    // Panics cannot unwind into Ruby
+    let result = codegen::method(|| {
+        // A fiber is started only once
+        let func = data
+            .with_borrow_mut(|data: &mut FiberClosure| data.func.take())
+            .unwrap();
+
+        func(argument)
+    });
+
+    result.value()
+}
+
+impl From<Value> for Fiber {
+    fn from(value: Value) -> Self {
+        Fiber {
+            value: value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Object for Fiber {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Fiber {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        fiber::is_fiber(object.value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Fiber"
+    }
+}
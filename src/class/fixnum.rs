@@ -1,7 +1,8 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::fixnum;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {Object, VerifiedObject};
 
@@ -9,6 +10,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Fixnum {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Fixnum {
@@ -59,7 +61,10 @@ impl Fixnum {
 
 impl From<Value> for Fixnum {
     fn from(value: Value) -> Self {
-        Fixnum { value: value }
+        Fixnum {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
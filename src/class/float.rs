@@ -1,7 +1,8 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::float;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {Object, VerifiedObject};
 
@@ -9,6 +10,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Float {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Float {
@@ -59,7 +61,10 @@ impl Float {
 
 impl From<Value> for Float {
     fn from(value: Value) -> Self {
-        Float { value: value }
+        Float {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
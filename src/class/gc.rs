@@ -1,6 +1,7 @@
 use binding::gc;
+use types::Value;
 
-use Object;
+use {Hash, Object, Symbol};
 
 /// Garbage collection
 pub struct GC;
@@ -21,4 +22,268 @@ impl GC {
     pub fn mark<T: Object>(object: &T) {
         gc::mark(object.value());
     }
+
+    /// Marks an object for garbage collection if the value looks like a pointer to a Ruby object.
+    ///
+    /// Unlike `GC::mark()`, it is safe to use with values which might not be Ruby objects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, RString, VM};
+    /// # VM::init();
+    ///
+    /// let object = RString::new("Hello");
+    ///
+    /// GC::mark_maybe(&object);
+    /// ```
+    pub fn mark_maybe<T: Object>(object: &T) {
+        gc::mark_maybe(object.value());
+    }
+
+    /// Marks all the values of a slice which look like pointers to Ruby objects.
+    ///
+    /// Useful for marking Rust collections of values in the `mark` function of a wrapped struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::types::Value;
+    /// use ruru::{Fixnum, GC, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let values: Vec<Value> = vec![Fixnum::new(1).value(), RString::new("Hello").value()];
+    ///
+    /// GC::mark_locations(&values);
+    /// ```
+    pub fn mark_locations(values: &[Value]) {
+        gc::mark_locations(values);
+    }
+
+    /// Registers an address of a `Value` as a root for garbage collection.
+    ///
+    /// The object which is stored at the address will not be garbage collected until the address
+    /// is unregistered with `GC::unregister()`.
+    ///
+    /// Prefer `Rooted` which registers and unregisters the address automatically.
+    ///
+    /// # Safety
+    ///
+    /// The address must stay valid (the `Value` must not be moved or dropped) until it is
+    /// unregistered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::types::Value;
+    /// use ruru::{GC, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let value: Box<Value> = Box::new(RString::new("Hello").value());
+    ///
+    /// unsafe {
+    ///     GC::register(&*value);
+    ///
+    ///     // `value` is safe from garbage collection here
+    ///
+    ///     GC::unregister(&*value);
+    /// }
+    /// ```
+    pub unsafe fn register(address: *const Value) {
+        gc::register(address);
+    }
+
+    /// Unregisters an address which was registered with `GC::register()`.
+    ///
+    /// # Safety
+    ///
+    /// The address must be previously registered with `GC::register()`.
+    pub unsafe fn unregister(address: *const Value) {
+        gc::unregister(address);
+    }
+
+    /// Starts garbage collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, VM};
+    /// # VM::init();
+    ///
+    /// GC::start();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// GC.start
+    /// ```
+    pub fn start() {
+        gc::start();
+    }
+
+    /// Enables garbage collection.
+    ///
+    /// Returns `true` if garbage collection was disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, VM};
+    /// # VM::init();
+    ///
+    /// GC::disable();
+    ///
+    /// assert!(GC::enable());
+    /// assert!(!GC::enable());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// GC.disable
+    ///
+    /// GC.enable == true
+    /// GC.enable == false
+    /// ```
+    pub fn enable() -> bool {
+        gc::enable()
+    }
+
+    /// Disables garbage collection.
+    ///
+    /// Returns `true` if garbage collection was already disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, VM};
+    /// # VM::init();
+    ///
+    /// assert!(!GC::disable());
+    /// assert!(GC::disable());
+    ///
+    /// GC::enable();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// GC.disable == false
+    /// GC.disable == true
+    ///
+    /// GC.enable
+    /// ```
+    pub fn disable() -> bool {
+        gc::disable()
+    }
+
+    /// Returns the number of times garbage collection has run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, VM};
+    /// # VM::init();
+    ///
+    /// let count = GC::count();
+    ///
+    /// GC::start();
+    ///
+    /// assert!(GC::count() > count);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// count = GC.count
+    ///
+    /// GC.start
+    ///
+    /// GC.count > count
+    /// ```
+    pub fn count() -> usize {
+        gc::count()
+    }
+
+    /// Returns statistics about garbage collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, GC, Object, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let stat = GC::stat();
+    /// let count = stat.at(&Symbol::new("count")).try_convert_to::<Fixnum>();
+    ///
+    /// assert!(count.is_ok());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// stat = GC.stat
+    ///
+    /// stat[:count]
+    /// ```
+    pub fn stat() -> Hash {
+        let hash = Hash::new();
+
+        gc::stat(hash.value());
+
+        hash
+    }
+
+    /// Returns a single value of the statistics about garbage collection.
+    ///
+    /// Raises `ArgumentError` if the key is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, VM};
+    /// # VM::init();
+    ///
+    /// let heap_live_slots = GC::stat_key("heap_live_slots");
+    ///
+    /// assert!(heap_live_slots > 0);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// GC.stat(:heap_live_slots)
+    /// ```
+    pub fn stat_key(key: &str) -> usize {
+        gc::stat(Symbol::new(key).value())
+    }
+
+    /// Informs the garbage collector about memory allocated or freed outside of Ruby heap.
+    ///
+    /// Positive `diff` means that memory was allocated, negative means that it was freed.
+    /// It allows garbage collector to take memory used by wrapped Rust structures into account
+    /// when deciding to start garbage collection.
+    ///
+    /// Available since Ruby 2.4 on Unix-like systems. The function does nothing for older versions
+    /// of Ruby and on other systems (e.g. Windows), because it is looked up at runtime with
+    /// `dlsym()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{GC, VM};
+    /// # VM::init();
+    ///
+    /// let buffer: Vec<u8> = Vec::with_capacity(1024 * 1024);
+    ///
+    /// GC::adjust_memory_usage(buffer.capacity() as isize);
+    ///
+    /// // ...
+    ///
+    /// GC::adjust_memory_usage(-(buffer.capacity() as isize));
+    /// ```
+    pub fn adjust_memory_usage(diff: isize) {
+        gc::adjust_memory_usage(diff);
+    }
 }
@@ -1,8 +1,9 @@
 use std::convert::From;
 use std::default::Default;
+use std::marker::PhantomData;
 
 use binding::hash;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {AnyObject, Object, VerifiedObject};
 
@@ -10,6 +11,7 @@ use {AnyObject, Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Hash {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Hash {
@@ -254,9 +256,7 @@ impl Hash {
 
 impl Clone for Hash {
     fn clone(&self) -> Hash {
-        Hash {
-            value: hash::dup(self.value()),
-        }
+        Hash::from(hash::dup(self.value()))
     }
 }
 
@@ -268,7 +268,10 @@ impl Default for Hash {
 
 impl From<Value> for Hash {
     fn from(value: Value) -> Self {
-        Hash { value: value }
+        Hash {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -1,7 +1,8 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::fixnum;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {Object, VerifiedObject};
 
@@ -9,6 +10,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Integer {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Integer {
@@ -59,7 +61,10 @@ impl Integer {
 
 impl From<Value> for Integer {
     fn from(value: Value) -> Self {
-        Integer { value: value }
+        Integer {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -2,15 +2,23 @@ pub mod any_object;
 pub mod array;
 pub mod boolean;
 pub mod class;
+pub mod condition_variable;
+pub mod embedded;
+pub mod enumerator;
+pub mod exception;
+pub mod fiber;
 pub mod fixnum;
 pub mod float;
 pub mod gc;
 pub mod hash;
 pub mod integer;
+pub mod mutex;
 pub mod nil_class;
+pub mod queue;
+pub mod rooted;
 pub mod rproc;
 pub mod string;
-pub mod traits;
-pub mod thread;
 pub mod symbol;
+pub mod thread;
+pub mod traits;
 pub mod vm;
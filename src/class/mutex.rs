@@ -0,0 +1,221 @@
+use std::convert::From;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+
+use binding::{class, thread};
+use codegen;
+use types::{NotSend, Value};
+
+use {Class, Object, VerifiedObject};
+
+/// `Mutex`
+///
+/// Unlike `std::sync::Mutex`, Ruby `Mutex` is known to the thread scheduler, so other Ruby
+/// threads keep running while the current thread is waiting for the lock. It should be used
+/// to synchronize Rust code with Ruby threads.
+#[derive(Debug, PartialEq)]
+pub struct Mutex {
+    value: Value,
+    _marker: NotSend,
+}
+
+impl Mutex {
+    /// Creates a new unlocked `Mutex`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Mutex, VM};
+    /// # VM::init();
+    ///
+    /// let mutex = Mutex::new();
+    ///
+    /// assert!(!mutex.is_locked());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// mutex = Mutex.new
+    ///
+    /// mutex.locked? == false
+    /// ```
+    pub fn new() -> Self {
+        Self::from(thread::mutex_new())
+    }
+
+    /// Locks the mutex, waits if it is locked by another thread.
+    ///
+    /// Raises `ThreadError` if the mutex is already locked by the current thread.
+    ///
+    /// The mutex is not unlocked automatically if an exception is raised before `unlock()`,
+    /// so `synchronize()` should be preferred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Mutex, VM};
+    /// # VM::init();
+    ///
+    /// let mutex = Mutex::new();
+    ///
+    /// mutex.lock();
+    ///
+    /// assert!(mutex.is_locked());
+    ///
+    /// mutex.unlock();
+    ///
+    /// assert!(!mutex.is_locked());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// mutex = Mutex.new
+    ///
+    /// mutex.lock
+    /// mutex.locked? == true
+    ///
+    /// mutex.unlock
+    /// mutex.locked? == false
+    /// ```
+    pub fn lock(&self) {
+        thread::mutex_lock(self.value());
+    }
+
+    /// Locks the mutex if it is not locked and returns `true`, otherwise returns `false`
+    /// immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Mutex, VM};
+    /// # VM::init();
+    ///
+    /// let mutex = Mutex::new();
+    ///
+    /// assert!(mutex.try_lock());
+    /// assert!(!mutex.try_lock());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// mutex = Mutex.new
+    ///
+    /// mutex.try_lock == true
+    /// mutex.try_lock == false
+    /// ```
+    pub fn try_lock(&self) -> bool {
+        thread::mutex_try_lock(self.value())
+    }
+
+    /// Unlocks the mutex.
+    ///
+    /// Raises `ThreadError` if the mutex is not locked by the current thread.
+    ///
+    /// See `lock()` for examples.
+    pub fn unlock(&self) {
+        thread::mutex_unlock(self.value());
+    }
+
+    /// Returns `true` if the mutex is locked by any thread.
+    ///
+    /// See `lock()` for examples.
+    pub fn is_locked(&self) -> bool {
+        thread::mutex_is_locked(self.value())
+    }
+
+    /// Locks the mutex, runs the closure and unlocks the mutex.
+    ///
+    /// The mutex is unlocked even if the closure is interrupted by an exception. A panic in the
+    /// closure is raised as `RuntimeError` after the mutex is unlocked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Mutex, VM};
+    /// # VM::init();
+    ///
+    /// let mutex = Mutex::new();
+    ///
+    /// let result = mutex.synchronize(|| {
+    ///     assert!(mutex.is_locked());
+    ///
+    ///     1 + 2
+    /// });
+    ///
+    /// assert_eq!(result, 3);
+    /// assert!(!mutex.is_locked());
+    ///
+    /// let result = VM::protect(|| {
+    ///     mutex.synchronize(|| panic!("the mutex is released"));
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// assert!(!mutex.is_locked());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// mutex = Mutex.new
+    ///
+    /// result = mutex.synchronize do
+    ///   mutex.locked? == true
+    ///
+    ///   1 + 2
+    /// end
+    ///
+    /// result == 3
+    /// mutex.locked? == false
+    /// ```
+    pub fn synchronize<F, R>(&self, func: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        // Panics cannot unwind through `rb_mutex_synchronize()`, so they are caught inside and
+        // raised after the mutex is unlocked
+        let result =
+            thread::mutex_synchronize(self.value(), || panic::catch_unwind(AssertUnwindSafe(func)));
+
+        match result {
+            Ok(result) => result,
+            Err(payload) => codegen::raise_panic(payload),
+        }
+    }
+}
+
+impl Default for Mutex {
+    fn default() -> Self {
+        Mutex::new()
+    }
+}
+
+impl From<Value> for Mutex {
+    fn from(value: Value) -> Self {
+        Mutex {
+            value: value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Object for Mutex {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Mutex {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        let mutex = Class::from_existing("Mutex");
+
+        class::is_kind_of(object.value(), mutex.value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Mutex"
+    }
+}
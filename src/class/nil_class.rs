@@ -1,8 +1,9 @@
 use std::convert::From;
 use std::default::Default;
+use std::marker::PhantomData;
 
 use binding::global::RubySpecialConsts;
-use types::{InternalValue, Value, ValueType};
+use types::{InternalValue, NotSend, Value, ValueType};
 
 use {Object, VerifiedObject};
 
@@ -10,6 +11,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct NilClass {
     value: Value,
+    _marker: NotSend,
 }
 
 impl NilClass {
@@ -42,7 +44,10 @@ impl Default for NilClass {
 
 impl From<Value> for NilClass {
     fn from(value: Value) -> Self {
-        NilClass { value: value }
+        NilClass {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -0,0 +1,207 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::class;
+use types::{NotSend, Value};
+
+use {AnyObject, Class, Fixnum, Object, VerifiedObject};
+
+/// `Queue`
+///
+/// A thread-safe FIFO queue for passing Ruby objects between Ruby threads.
+#[derive(Debug, PartialEq)]
+pub struct Queue {
+    value: Value,
+    _marker: NotSend,
+}
+
+impl Queue {
+    /// Creates a new empty `Queue`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Queue, VM};
+    /// # VM::init();
+    ///
+    /// let queue = Queue::new();
+    ///
+    /// assert!(queue.is_empty());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// queue = Queue.new
+    ///
+    /// queue.empty? == true
+    /// ```
+    pub fn new() -> Self {
+        let queue = Class::from_existing("Queue").new_instance(None);
+
+        Self::from(queue.value())
+    }
+
+    /// Adds an item to the end of the queue.
+    ///
+    /// Raises `ClosedQueueError` if the queue is closed.
+    ///
+    /// See `pop()` for examples.
+    pub fn push<T: Object>(&self, item: T) {
+        self.send("push", Some(&[item.to_any_object()]));
+    }
+
+    /// Removes an item from the beginning of the queue, waits for an item if the queue is
+    /// empty. Other threads are run meanwhile.
+    ///
+    /// Returns `nil` if the queue is closed and empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Gvl, Object, Queue, SendRooted, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let queue = Queue::new();
+    /// let producer_queue = SendRooted::new(Queue::from(queue.value()));
+    ///
+    /// Thread::new(move || {
+    ///     let producer_queue = producer_queue.get(&Gvl::current().unwrap());
+    ///
+    ///     producer_queue.push(Fixnum::new(1));
+    ///     producer_queue.push(Fixnum::new(2));
+    /// });
+    ///
+    /// assert_eq!(queue.pop().try_convert_to::<Fixnum>(), Ok(Fixnum::new(1)));
+    /// assert_eq!(queue.pop().try_convert_to::<Fixnum>(), Ok(Fixnum::new(2)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// queue = Queue.new
+    ///
+    /// Thread.new do
+    ///   queue.push(1)
+    ///   queue.push(2)
+    /// end
+    ///
+    /// queue.pop == 1
+    /// queue.pop == 2
+    /// ```
+    pub fn pop(&self) -> AnyObject {
+        self.send("pop", None)
+    }
+
+    /// Returns the number of items in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Queue, VM};
+    /// # VM::init();
+    ///
+    /// let queue = Queue::new();
+    ///
+    /// queue.push(Fixnum::new(1));
+    ///
+    /// assert_eq!(queue.length(), 1);
+    /// assert!(!queue.is_empty());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// queue = Queue.new
+    ///
+    /// queue.push(1)
+    ///
+    /// queue.length == 1
+    /// queue.empty? == false
+    /// ```
+    pub fn length(&self) -> usize {
+        let length = unsafe { self.send("length", None).to::<Fixnum>() };
+
+        length.to_i64() as usize
+    }
+
+    /// Returns `true` if the queue has no items.
+    ///
+    /// See `length()` for examples.
+    pub fn is_empty(&self) -> bool {
+        self.send("empty?", None).value().is_true()
+    }
+
+    /// Closes the queue, so no items can be added anymore.
+    ///
+    /// Threads waiting in `pop()` are woken up and receive `nil`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, Queue, VM};
+    /// # VM::init();
+    ///
+    /// let queue = Queue::new();
+    ///
+    /// queue.close();
+    ///
+    /// assert!(queue.is_closed());
+    /// assert!(queue.pop().is_nil());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// queue = Queue.new
+    ///
+    /// queue.close
+    ///
+    /// queue.closed? == true
+    /// queue.pop == nil
+    /// ```
+    pub fn close(&self) {
+        self.send("close", None);
+    }
+
+    /// Returns `true` if the queue is closed.
+    ///
+    /// See `close()` for examples.
+    pub fn is_closed(&self) -> bool {
+        self.send("closed?", None).value().is_true()
+    }
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl From<Value> for Queue {
+    fn from(value: Value) -> Self {
+        Queue {
+            value: value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Object for Queue {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Queue {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        let queue = Class::from_existing("Queue");
+
+        class::is_kind_of(object.value(), queue.value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Queue"
+    }
+}
@@ -0,0 +1,161 @@
+use std::convert::From;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use binding::thread;
+use types::Value;
+
+use {Gvl, Object, GC};
+
+/// A Ruby object which is protected from garbage collection
+///
+/// Ruby objects stored in Rust structures (thread-locals, collections, closures, etc) are not
+/// visible to the garbage collector and may be collected while they are still in use.
+///
+/// `Rooted` registers the object as a root for garbage collection and keeps it alive until
+/// the `Rooted` handle is dropped.
+///
+/// Like Ruby objects, `Rooted` is `!Send` and `!Sync`, because it must be used and dropped by
+/// a thread which holds GVL. Use `SendRooted` to store objects in statics or send them to other
+/// threads.
+///
+/// `Rooted<T>` dereferences to `T`, so all the methods of the object are available.
+///
+/// The handle does not give out mutable references to the object, because replacing the object
+/// would leave the new object unprotected. Methods which require `&mut self` can be called on
+/// a new handle to the same Ruby object, for example `Hash::from(rooted.value())`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use ruru::{Object, RString, Rooted, VM};
+/// # VM::init();
+///
+/// let mut cache = HashMap::new();
+///
+/// cache.insert("greeting", Rooted::new(RString::new("Hello")));
+///
+/// // The string is not garbage collected while it is stored in the cache
+///
+/// assert_eq!(cache["greeting"].to_str(), "Hello");
+///
+/// // The string can be garbage collected again
+/// cache.clear();
+/// ```
+#[derive(Debug)]
+pub struct Rooted<T: Object> {
+    object: T,
+    address: Box<Value>,
+}
+
+impl<T: Object> Rooted<T> {
+    /// Protects the object from garbage collection until the returned handle is dropped.
+    pub fn new(object: T) -> Self {
+        let address = Box::new(object.value());
+
+        unsafe { GC::register(&*address) };
+
+        Rooted {
+            object: object,
+            address: address,
+        }
+    }
+}
+
+impl<T: Object> Deref for Rooted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.object
+    }
+}
+
+impl<T: Object> Drop for Rooted<T> {
+    fn drop(&mut self) {
+        unsafe { GC::unregister(&*self.address) };
+    }
+}
+
+impl<T: Object> From<Value> for Rooted<T> {
+    fn from(value: Value) -> Self {
+        Rooted::new(T::from(value))
+    }
+}
+
+impl<T: Object> Object for Rooted<T> {
+    #[inline]
+    fn value(&self) -> Value {
+        self.object.value()
+    }
+}
+
+/// A Ruby object which is protected from garbage collection and can be shared between threads
+///
+/// Unlike `Rooted`, `SendRooted` is `Send` and `Sync`, so it can be stored in statics, sent
+/// through channels and moved to closures which are run without GVL or in other threads. The
+/// object itself can be used only by threads which hold GVL, so `get()` requires a `Gvl` token.
+///
+/// The object is unregistered when the handle is dropped by a thread which holds GVL. If the
+/// handle is dropped without GVL, the object cannot be unregistered safely, so it is never
+/// garbage collected.
+///
+/// # Examples
+///
+/// ```
+/// use ruru::{Object, RString, SendRooted, Thread, VM};
+/// # VM::init();
+///
+/// let greeting = SendRooted::new(RString::new("Hello"));
+///
+/// let length = Thread::call_without_gvl(
+///     move || {
+///         // The string cannot be used here, but the handle can be moved back to GVL
+///         Thread::call_with_gvl(move |gvl| greeting.get(&gvl).to_str().len())
+///     },
+///     None::<fn()>,
+/// );
+///
+/// assert_eq!(length, 5);
+/// ```
+#[derive(Debug)]
+pub struct SendRooted<T: Object> {
+    address: *mut Value,
+    _marker: PhantomData<fn() -> T>,
+}
+
+// The object is accessed only with `Gvl` and unregistered only by threads which hold GVL
+unsafe impl<T: Object> Send for SendRooted<T> {}
+unsafe impl<T: Object> Sync for SendRooted<T> {}
+
+impl<T: Object> SendRooted<T> {
+    /// Protects the object from garbage collection until the returned handle is dropped.
+    pub fn new(object: T) -> Self {
+        let address = Box::into_raw(Box::new(object.value()));
+
+        unsafe { GC::register(address) };
+
+        SendRooted {
+            address: address,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the object.
+    pub fn get(&self, _gvl: &Gvl) -> T {
+        T::from(unsafe { *self.address })
+    }
+}
+
+impl<T: Object> Drop for SendRooted<T> {
+    fn drop(&mut self) {
+        if thread::has_gvl() {
+            unsafe {
+                GC::unregister(self.address);
+
+                let _ = Box::from_raw(self.address);
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::rproc;
-use types::Value;
+use types::{NotSend, Value};
 use util;
 
 use {AnyObject, Class, Object, VerifiedObject};
@@ -10,6 +11,7 @@ use {AnyObject, Class, Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Proc {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Proc {
@@ -67,7 +69,10 @@ impl Proc {
 
 impl From<Value> for Proc {
     fn from(value: Value) -> Self {
-        Proc { value: value }
+        Proc {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -1,7 +1,8 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::string;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {Object, VerifiedObject};
 
@@ -9,6 +10,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct RString {
     value: Value,
+    _marker: NotSend,
 }
 
 impl RString {
@@ -61,6 +63,30 @@ impl RString {
         Self::from(string::new_utf8(string))
     }
 
+    /// Creates a new instance of Ruby `String` containing given `bytes`.
+    ///
+    /// The string has `ASCII-8BIT` encoding, so it can contain any binary data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{RString, VM};
+    /// # VM::init();
+    ///
+    /// let string = RString::from_bytes(&[0, 159, 146, 150]);
+    ///
+    /// assert_eq!(string.to_bytes_unchecked(), &[0, 159, 146, 150]);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// str = "\x00\x9F\x92\x96".b
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from(string::new_from_bytes(bytes))
+    }
+
     /// Retrieves underlying Rust `String` from Ruby `String` object.
     ///
     /// # Examples
@@ -169,6 +195,34 @@ impl RString {
         string::value_to_str_unchecked(value)
     }
 
+    /// Retrieves underlying bytes from Ruby `String` object.
+    ///
+    /// Unlike `to_str_unchecked()`, the bytes are not required to be valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{RString, VM};
+    /// # VM::init();
+    ///
+    /// let string = RString::new("Hello,\0World!");
+    ///
+    /// assert_eq!(string.to_bytes_unchecked(), b"Hello,\0World!");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// str = 'Hello,\0World!'
+    ///
+    /// str.bytes
+    /// ```
+    pub fn to_bytes_unchecked(&self) -> &[u8] {
+        let value = self.value();
+
+        string::value_to_bytes_unchecked(value)
+    }
+
     /// Returns the length of the string in bytes
     ///
     /// # Examples
@@ -200,7 +254,10 @@ impl RString {
 
 impl From<Value> for RString {
     fn from(value: Value) -> Self {
-        RString { value: value }
+        RString {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
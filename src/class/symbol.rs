@@ -1,8 +1,9 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::symbol;
 use binding::util;
-use types::{Value, ValueType};
+use types::{NotSend, Value, ValueType};
 
 use {Object, VerifiedObject};
 
@@ -10,6 +11,7 @@ use {Object, VerifiedObject};
 #[derive(Debug, PartialEq)]
 pub struct Symbol {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Symbol {
@@ -90,7 +92,10 @@ impl Symbol {
 
 impl From<Value> for Symbol {
     fn from(value: Value) -> Self {
-        Symbol { value: value }
+        Symbol {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
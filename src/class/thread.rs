@@ -1,52 +1,369 @@
 use std::convert::From;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
-use binding::thread;
-use types::Value;
+use binding::{thread, vm};
+use result::ThreadError;
+use types::{NotSend, Value};
 
 #[cfg(unix)]
-use types::RawFd;
+use std::io;
+#[cfg(unix)]
+use std::time::Duration;
+
+#[cfg(unix)]
+use types::{c_int, RawFd};
 
-use {Class, Object, VerifiedObject};
+use {AnyObject, Class, Exception, NilClass, Object, RString, Rooted, VerifiedObject};
 
 /// `Thread`
 #[derive(Debug, PartialEq)]
 pub struct Thread {
     value: Value,
+    _marker: NotSend,
 }
 
 impl Thread {
-    /// Creates a new green thread.
+    /// Creates a new green thread which runs the closure.
+    ///
+    /// Returns a `JoinHandle` which waits for the thread and returns the value returned by the
+    /// closure. Panics and Ruby exceptions in the thread are caught and returned by
+    /// `JoinHandle::join()` as `ThreadError`.
+    ///
+    /// The closure and its result are moved to another native thread, so they must be `Send`.
+    /// Ruby objects are `!Send` and cannot be captured by the closure, use `SendRooted` to pass
+    /// them to the thread (see `Queue::pop()` for an example):
+    ///
+    /// ```compile_fail
+    /// use ruru::{RString, Thread, VM};
+    /// # VM::init();
     ///
-    /// The returning value of the closure will be available as `#value` of the thread
+    /// let string = RString::new("Hello");
+    ///
+    /// Thread::new(move || string.to_str().len());
+    /// ```
     ///
     /// # Examples
     ///
     /// ```
-    /// use ruru::{Fixnum, Thread, VM};
+    /// use ruru::{Thread, VM};
     /// # VM::init();
     ///
-    /// Thread::new(|| {
+    /// let handle = Thread::new(|| {
     ///     let computation_result = 1 + 2;
     ///
-    ///     Fixnum::new(computation_result)
+    ///     computation_result
     /// });
+    ///
+    /// assert_eq!(handle.join().ok(), Some(3));
     /// ```
     ///
     /// Ruby:
     ///
     /// ```ruby
-    /// Thread.new do
+    /// thread = Thread.new do
     ///   computation_result = 1 + 2
     ///
     ///   computation_result
     /// end
+    ///
+    /// thread.value == 3
     /// ```
-    pub fn new<F, R>(func: F) -> Self
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<F, R>(func: F) -> JoinHandle<R>
     where
-        F: 'static + FnOnce() -> R,
-        R: Object,
+        F: 'static + Send + FnOnce() -> R,
+        R: 'static + Send,
     {
-        Self::from(thread::create(func))
+        let result = Arc::new(Mutex::new(None));
+        let thread_result = result.clone();
+
+        let thread = thread::create(move || {
+            *thread_result.lock().unwrap() = Some(run_protected(func));
+
+            NilClass::new().value()
+        });
+
+        JoinHandle {
+            thread: Rooted::new(Thread::from(thread)),
+            result: result,
+        }
+    }
+
+    /// Returns the currently executing thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::current();
+    ///
+    /// assert!(thread.is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.current
+    ///
+    /// thread.alive? == true
+    /// ```
+    pub fn current() -> Self {
+        Self::from(thread::current())
+    }
+
+    /// Waits for the thread to finish.
+    ///
+    /// If the thread has been terminated by an exception, the exception is raised again in the
+    /// current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::new(|| ()).thread();
+    ///
+    /// thread.join();
+    ///
+    /// assert!(!thread.is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { nil }
+    ///
+    /// thread.join
+    ///
+    /// thread.alive? == false
+    /// ```
+    pub fn join(&self) {
+        self.send("join", None);
+    }
+
+    /// Waits for the thread to finish and returns the value returned by its closure (Ruby
+    /// `Thread#value`).
+    ///
+    /// If the thread has been terminated by an exception, the exception is raised again in the
+    /// current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = VM::eval("Thread.new { 1 + 2 }").try_convert_to::<Thread>().unwrap();
+    ///
+    /// let result = thread.join_value().try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(result, Ok(Fixnum::new(3)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { 1 + 2 }
+    ///
+    /// thread.value == 3
+    /// ```
+    pub fn join_value(&self) -> AnyObject {
+        self.send("value", None)
+    }
+
+    /// Returns `true` if the thread is running or sleeping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::new(|| ()).thread();
+    ///
+    /// thread.join();
+    ///
+    /// assert!(!thread.is_alive());
+    /// assert!(Thread::current().is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { nil }
+    ///
+    /// thread.join
+    ///
+    /// thread.alive? == false
+    /// Thread.current.alive? == true
+    /// ```
+    pub fn is_alive(&self) -> bool {
+        self.send("alive?", None).value().is_true()
+    }
+
+    /// Terminates the thread.
+    ///
+    /// `ensure` blocks of the thread are executed before it is terminated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::new(|| {
+    ///     VM::eval("sleep");
+    /// }).thread();
+    ///
+    /// thread.kill();
+    /// thread.join();
+    ///
+    /// assert!(!thread.is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { sleep }
+    ///
+    /// thread.kill
+    /// thread.join
+    ///
+    /// thread.alive? == false
+    /// ```
+    pub fn kill(&self) {
+        thread::kill(self.value());
+    }
+
+    /// Marks a sleeping thread as eligible for scheduling.
+    ///
+    /// The thread is not switched to immediately. Raises Ruby `ThreadError` if the thread is
+    /// dead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::new(|| {
+    ///     VM::eval("sleep");
+    /// }).thread();
+    ///
+    /// // Wait until the thread falls asleep
+    /// while !thread.send("stop?", None).value().is_true() {
+    ///     VM::eval("Thread.pass");
+    /// }
+    ///
+    /// thread.wakeup();
+    /// thread.join();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { sleep }
+    ///
+    /// Thread.pass until thread.stop?
+    ///
+    /// thread.wakeup
+    /// thread.join
+    /// ```
+    pub fn wakeup(&self) {
+        thread::wakeup(self.value());
+    }
+
+    /// Returns the value of a thread-local (more precisely, fiber-local) variable.
+    ///
+    /// Returns `nil` if the variable is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let mut thread = Thread::current();
+    ///
+    /// thread.local_set("request_id", &RString::new("1a2b"));
+    ///
+    /// let request_id = thread.local_get("request_id").try_convert_to::<RString>();
+    ///
+    /// assert_eq!(request_id.map(|id| id.to_string()), Ok("1a2b".to_string()));
+    /// assert!(thread.local_get("user_id").is_nil());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.current
+    ///
+    /// thread[:request_id] = '1a2b'
+    ///
+    /// thread[:request_id] == '1a2b'
+    /// thread[:user_id].nil?
+    /// ```
+    pub fn local_get(&self, name: &str) -> AnyObject {
+        let result = thread::local_aref(self.value(), name);
+
+        AnyObject::from(result)
+    }
+
+    /// Sets the value of a thread-local (more precisely, fiber-local) variable.
+    ///
+    /// See `local_get()` for examples.
+    pub fn local_set<T: Object>(&mut self, name: &str, value: &T) -> AnyObject {
+        let result = thread::local_aset(self.value(), name, value.value());
+
+        AnyObject::from(result)
+    }
+
+    /// Returns the name of the thread or `None` if the name is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let mut thread = Thread::new(|| ()).thread();
+    ///
+    /// assert_eq!(thread.name(), None);
+    ///
+    /// thread.set_name("worker");
+    ///
+    /// assert_eq!(thread.name(), Some("worker".to_string()));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { nil }
+    ///
+    /// thread.name.nil?
+    ///
+    /// thread.name = 'worker'
+    ///
+    /// thread.name == 'worker'
+    /// ```
+    pub fn name(&self) -> Option<String> {
+        self.send("name", None)
+            .try_convert_to::<RString>()
+            .map(|name| name.to_string())
+            .ok()
+    }
+
+    /// Sets the name of the thread.
+    ///
+    /// See `name()` for examples.
+    pub fn set_name(&mut self, name: &str) {
+        self.send("name=", Some(&[RString::new(name).to_any_object()]));
     }
 
     /// Tells scheduler to switch to other threads while current thread is waiting for a
@@ -70,6 +387,116 @@ impl Thread {
         thread::wait_fd(fd);
     }
 
+    /// Waits until the file descriptor becomes readable or the timeout expires, other threads
+    /// are run meanwhile.
+    ///
+    /// Returns `false` if the timeout expired. If `timeout` is `None`, waits without a limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::UnixStream;
+    /// use std::time::Duration;
+    ///
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let (mut writer, reader) = UnixStream::pair().unwrap();
+    /// let timeout = Some(Duration::from_millis(10));
+    ///
+    /// assert_eq!(Thread::wait_readable(reader.as_raw_fd(), timeout).unwrap(), false);
+    ///
+    /// writer.write_all(b"ping").unwrap();
+    ///
+    /// assert_eq!(Thread::wait_readable(reader.as_raw_fd(), timeout).unwrap(), true);
+    /// ```
+    #[cfg(unix)]
+    pub fn wait_readable(fd: RawFd, timeout: Option<Duration>) -> io::Result<bool> {
+        Self::wait_for_fd(fd, FdEvents::READABLE, timeout).map(|events| events.readable)
+    }
+
+    /// Waits until the file descriptor becomes writable or the timeout expires, other threads
+    /// are run meanwhile.
+    ///
+    /// Returns `false` if the timeout expired. If `timeout` is `None`, waits without a limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::UnixStream;
+    /// use std::time::Duration;
+    ///
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let (writer, _reader) = UnixStream::pair().unwrap();
+    /// let timeout = Some(Duration::from_millis(10));
+    ///
+    /// assert_eq!(Thread::wait_writable(writer.as_raw_fd(), timeout).unwrap(), true);
+    /// ```
+    #[cfg(unix)]
+    pub fn wait_writable(fd: RawFd, timeout: Option<Duration>) -> io::Result<bool> {
+        Self::wait_for_fd(fd, FdEvents::WRITABLE, timeout).map(|events| events.writable)
+    }
+
+    /// Waits until any of the given events occur on the file descriptor or the timeout expires,
+    /// other threads are run meanwhile.
+    ///
+    /// Returns the events which occurred, they are empty if the timeout expired. If `timeout` is
+    /// `None`, waits without a limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::UnixStream;
+    /// use std::time::Duration;
+    ///
+    /// use ruru::{FdEvents, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let (mut writer, reader) = UnixStream::pair().unwrap();
+    /// let events = FdEvents { readable: true, writable: true, priority: false };
+    ///
+    /// writer.write_all(b"ping").unwrap();
+    ///
+    /// let fired = Thread::wait_for_fd(reader.as_raw_fd(), events, None).unwrap();
+    ///
+    /// assert_eq!(fired, events);
+    ///
+    /// let fired = Thread::wait_for_fd(
+    ///     reader.as_raw_fd(),
+    ///     FdEvents::PRIORITY,
+    ///     Some(Duration::from_millis(10))
+    /// ).unwrap();
+    ///
+    /// assert!(fired.is_empty());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// reader.wait(IO::READABLE | IO::WRITABLE)
+    /// ```
+    #[cfg(unix)]
+    pub fn wait_for_fd(
+        fd: RawFd,
+        events: FdEvents,
+        timeout: Option<Duration>,
+    ) -> io::Result<FdEvents> {
+        let result = thread::wait_for_single_fd(fd, events.to_waitfd(), timeout);
+
+        if result < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(FdEvents::from_waitfd(result))
+        }
+    }
+
     /// Release GVL for current thread.
     ///
     /// **Warning!** Due to MRI limitations, interaction with Ruby objects is not allowed while
@@ -81,6 +508,38 @@ impl Thread {
     ///
     /// GVL will be re-acquired when the closure is finished.
     ///
+    /// The closure must be `Send`, so Ruby objects (`AnyObject`, `RString`, classes created with
+    /// `class!` etc) cannot be captured by it:
+    ///
+    /// ```compile_fail
+    /// use ruru::{AnyObject, NilClass, Object, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let object: AnyObject = NilClass::new().to_any_object();
+    ///
+    /// Thread::call_without_gvl(move || object.is_nil(), None::<fn()>);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, Thread, VM};
+    ///
+    /// class!(Calculator);
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     let class = Class::new("Calculator", None);
+    ///     let calculator = Calculator::from(class.new_instance(None).value());
+    ///
+    ///     Thread::call_without_gvl(move || calculator.is_nil(), None::<fn()>);
+    /// }
+    /// ```
+    ///
+    /// However, the closure still can call functions which create new Ruby objects or run Ruby
+    /// code (like `RString::new()` or `VM::eval()`), which is not checked by the compiler. Use
+    /// `Thread::call_with_gvl()` to re-acquire GVL for them.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -117,31 +576,189 @@ impl Thread {
     /// ```
     pub fn call_without_gvl<F, R, G>(func: F, unblock_func: Option<G>) -> R
     where
-        F: 'static + FnOnce() -> R,
-        G: 'static + FnOnce(),
+        F: 'static + Send + FnOnce() -> R,
+        G: 'static + Send + FnOnce(),
+        R: Send,
     {
         thread::call_without_gvl(func, unblock_func)
     }
 
     pub fn call_without_gvl2<F, R, G>(func: F, unblock_func: Option<G>) -> R
     where
-        F: 'static + FnOnce() -> R,
-        G: 'static + FnOnce(),
+        F: 'static + Send + FnOnce() -> R,
+        G: 'static + Send + FnOnce(),
+        R: Send,
     {
         thread::call_without_gvl2(func, unblock_func)
     }
 
+    /// Releases GVL for current thread and runs the closure, which can be cancelled by Ruby.
+    ///
+    /// The closure receives a `CancellationToken`, which is cancelled when Ruby interrupts the
+    /// thread (for example, by `Thread#kill`, `Timeout.timeout` or a signal like `SIGINT`). Long
+    /// computations should check the token periodically and return as soon as possible when it is
+    /// cancelled. The interrupt is handled when the closure returns and GVL is re-acquired, so
+    /// the exception of `Timeout.timeout` is raised after that.
+    ///
+    /// The same restrictions as for `call_without_gvl()` apply to the closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, Thread, VM};
+    ///
+    /// class!(Calculator);
+    ///
+    /// methods!(
+    ///     Calculator,
+    ///     itself,
+    ///
+    ///     fn heavy_computation() -> Option<u64> {
+    ///
+    ///         Thread::call_without_gvl_cancellable(|token| {
+    ///             let mut sum: u64 = 0;
+    ///
+    ///             for i in 0..1_000_000_000 {
+    ///                 if token.is_cancelled() {
+    ///                     return None;
+    ///                 }
+    ///
+    ///                 sum += i;
+    ///             }
+    ///
+    ///             Some(sum)
+    ///         })
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Calculator", None).define(|itself| {
+    ///         itself.def("heavy_computation", heavy_computation);
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// require 'timeout'
+    ///
+    /// # The computation is cancelled and `Timeout::Error` is raised after 1 second
+    /// Timeout.timeout(1) { Calculator.new.heavy_computation }
+    /// ```
+    pub fn call_without_gvl_cancellable<F, R>(func: F) -> R
+    where
+        F: Send + FnOnce(&CancellationToken) -> R,
+        R: Send,
+    {
+        let token = CancellationToken {
+            cancelled: AtomicBool::new(false),
+        };
+
+        thread::call_without_gvl_cancellable(|| func(&token), &token.cancelled)
+    }
+
+    /// Handles pending interrupts of the current thread.
+    ///
+    /// Long computations which hold GVL should call it periodically to let Ruby kill the thread
+    /// and raise exceptions of `Timeout.timeout`, `Thread#raise` or signal handlers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let mut sum: u64 = 0;
+    ///
+    /// for i in 0..1_000_000 {
+    ///     if i % 1000 == 0 {
+    ///         Thread::check_ints();
+    ///     }
+    ///
+    ///     sum += i;
+    /// }
+    /// ```
+    pub fn check_ints() {
+        thread::check_ints();
+    }
+
+    /// Re-acquires GVL in a closure which is run without GVL and runs `func`.
+    ///
+    /// `func` receives a `Gvl` token, which gives access to objects of `SendRooted` handles.
+    /// The returned value must be `Send`, so Ruby objects cannot be returned to the code which
+    /// is run without GVL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// fn log(message: &str) {
+    ///     VM::eval(&format!("$stderr.puts({:?})", message));
+    /// }
+    ///
+    /// let result = Thread::call_without_gvl(
+    ///     || {
+    ///         let result = 2 * 2;
+    ///
+    ///         Thread::call_with_gvl(|_gvl| log("Computation is finished"));
+    ///
+    ///         result
+    ///     },
+    ///     None::<fn()>,
+    /// );
+    ///
+    /// assert_eq!(result, 4);
+    /// ```
     pub fn call_with_gvl<F, R>(func: F) -> R
     where
-        F: 'static + FnOnce() -> R,
+        F: 'static + FnOnce(Gvl) -> R,
+        R: Send,
     {
-        thread::call_with_gvl(func)
+        thread::call_with_gvl(|| func(Gvl::new()))
+    }
+}
+
+// Runs the closure of a thread catching panics and exceptions
+fn run_protected<F, R>(func: F) -> Result<R, ThreadError>
+where
+    F: FnOnce() -> R,
+{
+    let mut result = None;
+
+    // The result is not set only if the closure is interrupted by an exception
+    let _ = vm::protect(|| {
+        result = Some(panic::catch_unwind(AssertUnwindSafe(func)));
+    });
+
+    match result {
+        Some(Ok(result)) => Ok(result),
+        Some(Err(payload)) => Err(ThreadError::Panic(payload)),
+        None => {
+            let errinfo = AnyObject::from(vm::take_errinfo());
+
+            // Killing the thread interrupts the closure too, but `$!` is not an exception in
+            // this case. The exception is stored in Rust memory until the thread is joined,
+            // so it is protected from GC.
+            match errinfo.try_convert_to::<Exception>() {
+                Ok(exception) => Err(ThreadError::Exception(Rooted::new(exception))),
+                Err(_) => Err(ThreadError::Killed),
+            }
+        }
     }
 }
 
 impl From<Value> for Thread {
     fn from(value: Value) -> Self {
-        Thread { value: value }
+        Thread {
+            value: value,
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -161,3 +778,212 @@ impl VerifiedObject for Thread {
         "Error converting to Thread"
     }
 }
+
+/// An owned permission to join a thread created by `Thread::new()`
+///
+/// The Ruby thread is protected from garbage collection until the handle is dropped.
+pub struct JoinHandle<R> {
+    thread: Rooted<Thread>,
+    result: Arc<Mutex<Option<Result<R, ThreadError>>>>,
+}
+
+impl<R> JoinHandle<R> {
+    /// Returns the Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let handle = Thread::new(|| 1 + 2);
+    ///
+    /// handle.thread().set_name("worker");
+    ///
+    /// assert_eq!(handle.thread().name(), Some("worker".to_string()));
+    /// ```
+    pub fn thread(&self) -> Thread {
+        Thread::from(self.thread.value())
+    }
+
+    /// Waits for the thread to finish and returns the value returned by its closure.
+    ///
+    /// Other Ruby threads are run while the current thread is waiting.
+    ///
+    /// Returns an error if the closure panicked or raised an exception, or if the thread has
+    /// been killed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::result::ThreadError;
+    /// use ruru::{Thread, VM};
+    /// # VM::init();
+    ///
+    /// let handle = Thread::new(|| "Hello".to_string());
+    ///
+    /// assert_eq!(handle.join().ok(), Some("Hello".to_string()));
+    ///
+    /// let handle = Thread::new(|| {
+    ///     VM::eval("raise 'Something went wrong'");
+    /// });
+    ///
+    /// match handle.join() {
+    ///     Err(ThreadError::Exception(exception)) => {
+    ///         assert_eq!(exception.message(), "Something went wrong");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { 'Hello' }
+    ///
+    /// thread.value == 'Hello'
+    ///
+    /// thread = Thread.new { raise 'Something went wrong' }
+    ///
+    /// begin
+    ///   thread.value
+    /// rescue => exception
+    ///   exception.message == 'Something went wrong'
+    /// end
+    /// ```
+    pub fn join(self) -> Result<R, ThreadError> {
+        self.thread.join();
+
+        let result = self.result.lock().unwrap().take();
+
+        // The closure is not finished if the thread is killed before it is started
+        result.unwrap_or(Err(ThreadError::Killed))
+    }
+}
+
+/// Events of a file descriptor which `Thread::wait_for_fd()` waits for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FdEvents {
+    /// Data can be read
+    pub readable: bool,
+    /// Data can be written
+    pub writable: bool,
+    /// Urgent data can be read
+    pub priority: bool,
+}
+
+impl FdEvents {
+    /// Only `readable` event.
+    pub const READABLE: FdEvents = FdEvents {
+        readable: true,
+        writable: false,
+        priority: false,
+    };
+
+    /// Only `writable` event.
+    pub const WRITABLE: FdEvents = FdEvents {
+        readable: false,
+        writable: true,
+        priority: false,
+    };
+
+    /// Only `priority` event.
+    pub const PRIORITY: FdEvents = FdEvents {
+        readable: false,
+        writable: false,
+        priority: true,
+    };
+
+    /// Returns `true` if none of the events is set.
+    pub fn is_empty(&self) -> bool {
+        !(self.readable || self.writable || self.priority)
+    }
+
+    #[cfg(unix)]
+    fn to_waitfd(self) -> c_int {
+        let mut events = 0;
+
+        if self.readable {
+            events |= thread::WAITFD_IN;
+        }
+
+        if self.writable {
+            events |= thread::WAITFD_OUT;
+        }
+
+        if self.priority {
+            events |= thread::WAITFD_PRI;
+        }
+
+        events
+    }
+
+    #[cfg(unix)]
+    fn from_waitfd(events: c_int) -> Self {
+        FdEvents {
+            readable: events & thread::WAITFD_IN != 0,
+            writable: events & thread::WAITFD_OUT != 0,
+            priority: events & thread::WAITFD_PRI != 0,
+        }
+    }
+}
+
+/// A token which is cancelled when Ruby interrupts a thread
+///
+/// See `Thread::call_without_gvl_cancellable()`.
+pub struct CancellationToken {
+    cancelled: AtomicBool,
+}
+
+impl CancellationToken {
+    /// Returns `true` if the computation should be stopped.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// A proof that the current thread holds GVL
+///
+/// Ruby objects can be used only by threads which hold GVL. Ruby objects are `!Send`, so they
+/// cannot be moved to closures which are run without GVL, but `SendRooted` handles can. The
+/// token is required to get objects from such handles.
+///
+/// Other functions of ruru do not require the token, so functions which do not receive an
+/// object (like `RString::new()`) can still be called without GVL by mistake.
+///
+/// `Gvl` is `!Send` too. It can be obtained from `Gvl::current()`, which checks that the current
+/// thread holds GVL, or in `Thread::call_with_gvl()`.
+#[derive(Clone, Copy, Debug)]
+pub struct Gvl {
+    _marker: NotSend,
+}
+
+impl Gvl {
+    fn new() -> Self {
+        Gvl {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the token if the current thread holds GVL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Gvl, Thread, VM};
+    /// # VM::init();
+    ///
+    /// assert!(Gvl::current().is_some());
+    ///
+    /// let has_gvl = Thread::call_without_gvl(|| Gvl::current().is_some(), None::<fn()>);
+    ///
+    /// assert!(!has_gvl);
+    /// ```
+    pub fn current() -> Option<Self> {
+        if thread::has_gvl() {
+            Some(Gvl::new())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash as StdHash};
+
+use binding::{fixnum, float};
+use result::{Error, Result};
+use types::ValueType;
+
+use {AnyObject, Array, Boolean, Hash, Object, RString, VerifiedObject};
+
+/// Conversion of Ruby objects to native Rust values
+///
+/// The trait is implemented for all types which implement `VerifiedObject` and for the
+/// following native types:
+///
+///  - `i8`, `i16`, `i32`, `i64`, `isize`, `u8`, `u16`, `u32`, `u64`, `usize` from `Integer`;
+///  - `f32`, `f64` from `Float` or `Integer`;
+///  - `bool` from `true` or `false`;
+///  - `String` from `String`;
+///  - `()` from `nil`;
+///  - `Option<T>` from `nil` or an object convertible to `T`;
+///  - `Vec<T>` from `Array`;
+///  - tuples from `Array` of the same length;
+///  - `HashMap<K, V>` from `Hash`.
+///
+/// The conversion never raises Ruby exceptions. If the object has an unexpected type or the
+/// value does not fit into the native type, an `Error` is returned.
+///
+/// Arguments of methods defined with the `methods!` macro are converted using this trait,
+/// so the methods can receive native Rust types directly.
+///
+/// # Examples
+///
+/// ```
+/// use ruru::result::Error;
+/// use ruru::{Array, Fixnum, FromRuby, Object, RString, VM};
+/// # VM::init();
+///
+/// let array = Array::new().push(Fixnum::new(1)).push(Fixnum::new(2)).to_any_object();
+///
+/// assert_eq!(Vec::<u8>::from_ruby(&array), Ok(vec![1, 2]));
+/// assert_eq!(<(i64, i64)>::from_ruby(&array), Ok((1, 2)));
+///
+/// let string = RString::new("Hello").to_any_object();
+///
+/// assert_eq!(String::from_ruby(&string), Ok("Hello".to_string()));
+/// assert!(i64::from_ruby(&string).is_err());
+///
+/// let big_number = Fixnum::new(1000).to_any_object();
+/// let expected_error = Error::RangeError("Integer 1000 is out of range for u8".to_string());
+///
+/// assert_eq!(u8::from_ruby(&big_number), Err(expected_error));
+/// ```
+pub trait FromRuby: Sized {
+    /// Converts a Ruby object to the native value.
+    fn from_ruby(object: &AnyObject) -> Result<Self>;
+}
+
+impl<T: VerifiedObject> FromRuby for T {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        object.try_convert_to::<T>()
+    }
+}
+
+fn is_integer(object: &AnyObject) -> bool {
+    let ty = object.ty();
+
+    ty == ValueType::Fixnum || ty == ValueType::Bignum
+}
+
+fn integer_type_error() -> Error {
+    Error::TypeError("Error converting to Integer".to_string())
+}
+
+// Integers are converted to strings and checked by C functions, because the methods of
+// `Integer` can be redefined
+fn out_of_range_error(object: &AnyObject, native_type: &str) -> Error {
+    let integer = if object.ty() == ValueType::Fixnum {
+        fixnum::fixnum_to_string(object.value())
+    } else {
+        fixnum::bignum_to_string(object.value())
+    };
+
+    Error::RangeError(format!(
+        "Integer {} is out of range for {}",
+        RString::from(integer).to_string(),
+        native_type
+    ))
+}
+
+fn integer_to_i64(object: &AnyObject, native_type: &str) -> Result<i64> {
+    if !is_integer(object) {
+        return Err(integer_type_error());
+    }
+
+    match fixnum::integer_to_abs_u64(object.value()) {
+        (abs, 0) | (abs, 1) if abs <= i64::MAX as u64 => Ok(abs as i64),
+        (abs, -1) if abs <= 1 << 63 => Ok((abs as i64).wrapping_neg()),
+        _ => Err(out_of_range_error(object, native_type)),
+    }
+}
+
+fn integer_to_u64(object: &AnyObject, native_type: &str) -> Result<u64> {
+    if !is_integer(object) {
+        return Err(integer_type_error());
+    }
+
+    match fixnum::integer_to_abs_u64(object.value()) {
+        (abs, 0) | (abs, 1) => Ok(abs),
+        _ => Err(out_of_range_error(object, native_type)),
+    }
+}
+
+macro_rules! signed_from_ruby {
+    ($($native: ident),*) => {
+        $(
+            impl FromRuby for $native {
+                fn from_ruby(object: &AnyObject) -> Result<Self> {
+                    let native_type = stringify!($native);
+                    let value = integer_to_i64(object, native_type)?;
+
+                    if value < $native::MIN as i64 || value > $native::MAX as i64 {
+                        return Err(out_of_range_error(object, native_type));
+                    }
+
+                    Ok(value as $native)
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! unsigned_from_ruby {
+    ($($native: ident),*) => {
+        $(
+            impl FromRuby for $native {
+                fn from_ruby(object: &AnyObject) -> Result<Self> {
+                    let native_type = stringify!($native);
+                    let value = integer_to_u64(object, native_type)?;
+
+                    if value > $native::MAX as u64 {
+                        return Err(out_of_range_error(object, native_type));
+                    }
+
+                    Ok(value as $native)
+                }
+            }
+        )*
+    }
+}
+
+signed_from_ruby!(i8, i16, i32, i64, isize);
+unsigned_from_ruby!(u8, u16, u32, u64, usize);
+
+impl FromRuby for f64 {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        match object.ty() {
+            ValueType::Float | ValueType::Fixnum | ValueType::Bignum => {
+                Ok(float::num_to_float(object.value()))
+            }
+            _ => Err(Error::TypeError("Error converting to Float".to_string())),
+        }
+    }
+}
+
+impl FromRuby for f32 {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        f64::from_ruby(object).map(|value| value as f32)
+    }
+}
+
+impl FromRuby for bool {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        object
+            .try_convert_to::<Boolean>()
+            .map(|boolean| boolean.to_bool())
+    }
+}
+
+impl FromRuby for String {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        object
+            .try_convert_to::<RString>()
+            .map(|string| string.to_string())
+    }
+}
+
+impl FromRuby for () {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        if object.is_nil() {
+            Ok(())
+        } else {
+            Err(Error::TypeError("Error converting to NilClass".to_string()))
+        }
+    }
+}
+
+impl<T: FromRuby> FromRuby for Option<T> {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        if object.is_nil() {
+            Ok(None)
+        } else {
+            T::from_ruby(object).map(Some)
+        }
+    }
+}
+
+impl<T: FromRuby> FromRuby for Vec<T> {
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        let array = object.try_convert_to::<Array>()?;
+
+        array.into_iter().map(|item| T::from_ruby(&item)).collect()
+    }
+}
+
+impl<K, V, S> FromRuby for HashMap<K, V, S>
+where
+    K: FromRuby + Eq + StdHash,
+    V: FromRuby,
+    S: BuildHasher + Default,
+{
+    fn from_ruby(object: &AnyObject) -> Result<Self> {
+        let hash = object.try_convert_to::<Hash>()?;
+        let mut map = HashMap::with_capacity_and_hasher(hash.length(), S::default());
+        let mut error = None;
+
+        hash.each(|key, value| {
+            if error.is_some() {
+                return;
+            }
+
+            match (K::from_ruby(&key), V::from_ruby(&value)) {
+                (Ok(key), Ok(value)) => {
+                    map.insert(key, value);
+                }
+                (Err(err), _) | (_, Err(err)) => error = Some(err),
+            }
+        });
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(map),
+        }
+    }
+}
+
+macro_rules! tuple_from_ruby {
+    ($length: expr; $($name: ident: $index: expr),+) => {
+        impl<$($name: FromRuby),+> FromRuby for ($($name,)+) {
+            fn from_ruby(object: &AnyObject) -> Result<Self> {
+                let array = object.try_convert_to::<Array>()?;
+
+                if array.length() != $length {
+                    return Err(Error::TypeError(format!(
+                        "Error converting Array of length {} to a tuple of length {}",
+                        array.length(),
+                        $length
+                    )));
+                }
+
+                Ok(($($name::from_ruby(&array.at($index))?,)+))
+            }
+        }
+    }
+}
+
+tuple_from_ruby!(1; A: 0);
+tuple_from_ruby!(2; A: 0, B: 1);
+tuple_from_ruby!(3; A: 0, B: 1, C: 2);
+tuple_from_ruby!(4; A: 0, B: 1, C: 2, D: 3);
+tuple_from_ruby!(5; A: 0, B: 1, C: 2, D: 3, E: 4);
+tuple_from_ruby!(6; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+tuple_from_ruby!(7; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+tuple_from_ruby!(8; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
@@ -0,0 +1,72 @@
+use result::Result;
+
+/// Rust structures which can be dumped and loaded by Ruby `Marshal`
+///
+/// The wrapped data is dumped to bytes, which are stored by `Marshal.dump` as a `String`.
+/// `Marshal.load` passes the bytes back to `load()` and wraps the result into a new object.
+///
+/// To register `_dump` and `_load` methods for the class of wrapped objects, create the wrapper
+/// by `wrappable_struct!` with the `marshal` option and pass it to `Class::define_marshal()`.
+///
+/// If the `serde` feature is enabled, the trait can be implemented with
+/// `ruru::serde::to_bytes()` and `ruru::serde::from_bytes()` for serializable structs.
+///
+/// Errors returned by `dump()` and `load()` are raised as Ruby exceptions.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate ruru;
+///
+/// use ruru::result::{Error, Result};
+/// use ruru::{AnyObject, Class, MarshalData, Object, VM};
+///
+/// pub struct Counter {
+///     count: u32,
+/// }
+///
+/// impl MarshalData for Counter {
+///     fn dump(&self) -> Result<Vec<u8>> {
+///         Ok(self.count.to_string().into_bytes())
+///     }
+///
+///     fn load(bytes: &[u8]) -> Result<Self> {
+///         String::from_utf8_lossy(bytes)
+///             .parse()
+///             .map(|count| Counter { count: count })
+///             .map_err(|_| Error::ArgumentError("Invalid counter".to_string()))
+///     }
+/// }
+///
+/// wrappable_struct!(Counter, CounterWrapper, COUNTER_WRAPPER, marshal);
+///
+/// fn main() {
+///     # VM::init();
+///     let data_class = Class::from_existing("Data");
+///     let mut counter_class = Class::new("Counter", Some(&data_class));
+///
+///     counter_class.define_marshal(&COUNTER_WRAPPER);
+///
+///     let counter: AnyObject = counter_class.wrap_data(Counter { count: 5 }, &COUNTER_WRAPPER);
+///
+///     let marshal = Class::from_existing("Marshal");
+///     let dump = marshal.send("dump", Some(&[counter]));
+///     let counter = marshal.send("load", Some(&[dump]));
+///
+///     assert_eq!(counter.get_data(&COUNTER_WRAPPER).count, 5);
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// # `counter` wraps `Counter { count: 5 }`
+/// counter = Marshal.load(Marshal.dump(counter))
+/// ```
+pub trait MarshalData: Sized {
+    /// Converts the struct to bytes.
+    fn dump(&self) -> Result<Vec<u8>>;
+
+    /// Restores the struct from bytes returned by `dump()`.
+    fn load(bytes: &[u8]) -> Result<Self>;
+}
@@ -1,2 +1,6 @@
+pub mod from_ruby;
+pub mod marshal_data;
 pub mod object;
+pub mod to_ruby;
+pub mod typed_data;
 pub mod verified_object;
@@ -1,14 +1,17 @@
 use std::convert::From;
 
 use binding::class;
+use binding::enumerator;
 use binding::global::ValueType;
 use binding::util as binding_util;
+use binding::vm;
+use class::traits::typed_data;
 use result::{Error, Result};
-use typed_data::DataTypeWrapper;
+use typed_data::{DataTypeWrapper, Ref, RefMut};
 use types::{Callback, Value};
 use util;
 
-use {AnyObject, Boolean, Class, VerifiedObject};
+use {AnyObject, Boolean, Class, Enumerator, Exception, Hash, RString, TypedData, VerifiedObject};
 
 /// `Object`
 ///
@@ -124,13 +127,24 @@ pub trait Object: From<Value> {
     ///
     /// See the documentation for `wrappable_struct!` macro for more information.
     ///
+    /// # Migrating to `TypedData`
+    ///
+    /// The returned reference is shared, use `get_data_mut()` to modify the data. Both functions
+    /// do not track borrows, so nothing prevents two `Object`s pointing to the same Ruby object
+    /// from getting aliasing references. Implement `TypedData` for the struct instead of using
+    /// `wrappable_struct!` to get borrow-checked access:
+    ///
+    ///  - `wrap_data(data, &WRAPPER)` becomes `data.wrap()`
+    ///  - `get_data(&WRAPPER)` becomes `borrow::<T>()` or `with_borrow(|data: &T| ...)`
+    ///  - `get_data_mut(&WRAPPER)` becomes `borrow_mut::<T>()` or
+    ///    `with_borrow_mut(|data: &mut T| ...)`
+    ///
     /// # Examples
     ///
     /// Wrap `Server` structs to `RubyServer` objects
     ///
     /// ```
     /// #[macro_use] extern crate ruru;
-    /// #[macro_use] extern crate lazy_static;
     ///
     /// use ruru::{AnyObject, Class, Fixnum, Object, RString, VM};
     ///
@@ -169,17 +183,17 @@ pub trait Object: From<Value> {
     ///         let server = Server::new(host.unwrap().to_string(),
     ///                                  port.unwrap().to_i64() as u16);
     ///
-    ///         Class::from_existing("RubyServer").wrap_data(server, &*SERVER_WRAPPER)
+    ///         Class::from_existing("RubyServer").wrap_data(server, &SERVER_WRAPPER)
     ///     }
     ///
     ///     fn ruby_server_host() -> RString {
-    ///         let host = itself.get_data(&*SERVER_WRAPPER).host();
+    ///         let host = itself.get_data(&SERVER_WRAPPER).host();
     ///
     ///         RString::new(host)
     ///     }
     ///
     ///     fn ruby_server_port() -> Fixnum {
-    ///         let port = itself.get_data(&*SERVER_WRAPPER).port();
+    ///         let port = itself.get_data(&SERVER_WRAPPER).port();
     ///
     ///         Fixnum::new(port as i64)
     ///     }
@@ -215,6 +229,265 @@ pub trait Object: From<Value> {
         class::get_data(self.value(), wrapper)
     }
 
+    /// Checks whether the object wraps a Rust structure of the given wrapper.
+    ///
+    /// Unlike `get_data()`, it does not raise `TypeError` for other objects. Objects which
+    /// wrap a child struct (see `parent` option of `wrappable_struct!`) are accepted as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{AnyObject, Class, Fixnum, Object, VM};
+    ///
+    /// pub struct Shape {
+    ///     name: String,
+    /// }
+    ///
+    /// pub struct Circle {
+    ///     shape: Shape,
+    ///     radius: f64,
+    /// }
+    ///
+    /// impl AsMut<Shape> for Circle {
+    ///     fn as_mut(&mut self) -> &mut Shape {
+    ///         &mut self.shape
+    ///     }
+    /// }
+    ///
+    /// wrappable_struct!(Shape, ShapeWrapper, SHAPE_WRAPPER);
+    /// wrappable_struct!(Circle, CircleWrapper, CIRCLE_WRAPPER, parent(SHAPE_WRAPPER));
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     let data_class = Class::from_existing("Data");
+    ///     let shape_class = Class::new("Shape", Some(&data_class));
+    ///     let circle_class = Class::new("Circle", Some(&shape_class));
+    ///
+    ///     let shape = Shape { name: "circle".to_string() };
+    ///     let circle = Circle { shape: shape, radius: 1.0 };
+    ///     let circle: AnyObject = circle_class.wrap_data(circle, &CIRCLE_WRAPPER);
+    ///
+    ///     assert!(circle.is_typed_data(&CIRCLE_WRAPPER));
+    ///     assert!(circle.is_typed_data(&SHAPE_WRAPPER));
+    ///     assert!(!Fixnum::new(1).is_typed_data(&SHAPE_WRAPPER));
+    ///
+    ///     assert_eq!(circle.get_data(&SHAPE_WRAPPER).name, "circle");
+    ///     assert_eq!(circle.get_data(&CIRCLE_WRAPPER).radius, 1.0);
+    /// }
+    /// ```
+    fn is_typed_data<T>(&self, wrapper: &DataTypeWrapper<T>) -> bool {
+        class::is_typed_data(self.value(), wrapper.data_type())
+    }
+
+    /// Immutably borrows the Rust structure which is wrapped into a Ruby object with `TypedData`.
+    ///
+    /// Raises `TypeError` if the object does not wrap a `T` and `RuntimeError` if the data is
+    /// currently borrowed mutably.
+    ///
+    /// The data is borrowed until the returned `Ref` is dropped. Exceptions skip destructors of
+    /// Rust values, so if an exception is raised while the `Ref` is alive, the borrow is released
+    /// when the exception is rescued by `VM::protect()` or leaves a method defined with
+    /// `methods!` or `#[ruby_methods]`. Prefer `with_borrow()` if any Ruby code is called while
+    /// the data is borrowed.
+    ///
+    /// See `TypedData` for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, TypedData, VM};
+    ///
+    /// pub struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// impl TypedData for Point {
+    ///     fn class() -> Class {
+    ///         Class::from_existing("Point")
+    ///     }
+    ///
+    ///     typed_data_type!(Point);
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Point", None);
+    ///
+    ///     let point = Point { x: 1, y: 2 }.wrap();
+    ///
+    ///     let first = point.borrow::<Point>();
+    ///     let second = point.borrow::<Point>();
+    ///
+    ///     assert_eq!(first.x + second.y, 3);
+    /// }
+    /// ```
+    fn borrow<T: TypedData>(&self) -> Ref<'_, T> {
+        typed_data::borrow(self.value())
+    }
+
+    /// Mutably borrows the Rust structure which is wrapped into a Ruby object with `TypedData`.
+    ///
+    /// Raises `TypeError` if the object does not wrap a `T` and `RuntimeError` if the data is
+    /// currently borrowed.
+    ///
+    /// The data is borrowed until the returned `RefMut` is dropped. If an exception is raised
+    /// while the `RefMut` is alive, the borrow is released when the exception is rescued by
+    /// `VM::protect()` or leaves a method defined with `methods!` or `#[ruby_methods]`. Prefer
+    /// `with_borrow_mut()` if any Ruby code is called while the data is borrowed.
+    ///
+    /// See `TypedData` for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, TypedData, VM};
+    ///
+    /// pub struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// impl TypedData for Point {
+    ///     fn class() -> Class {
+    ///         Class::from_existing("Point")
+    ///     }
+    ///
+    ///     typed_data_type!(Point);
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Point", None);
+    ///
+    ///     let point = Point { x: 1, y: 2 }.wrap();
+    ///
+    ///     point.borrow_mut::<Point>().x = 10;
+    ///
+    ///     assert_eq!(point.borrow::<Point>().x, 10);
+    /// }
+    /// ```
+    fn borrow_mut<T: TypedData>(&self) -> RefMut<'_, T> {
+        typed_data::borrow_mut(self.value())
+    }
+
+    /// Immutably borrows the Rust structure which is wrapped into a Ruby object with `TypedData`
+    /// and passes it to the closure.
+    ///
+    /// Raises `TypeError` if the object does not wrap a `T` and `RuntimeError` if the data is
+    /// currently borrowed mutably.
+    ///
+    /// The data is released before exceptions raised in the closure are propagated (and before
+    /// panics are resumed), so the closure can safely call Ruby code.
+    ///
+    /// See `TypedData` for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, TypedData, VM};
+    ///
+    /// pub struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// impl TypedData for Point {
+    ///     fn class() -> Class {
+    ///         Class::from_existing("Point")
+    ///     }
+    ///
+    ///     typed_data_type!(Point);
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Point", None);
+    ///
+    ///     let point = Point { x: 1, y: 2 }.wrap();
+    ///
+    ///     // The exception does not leave the point borrowed
+    ///     let result = VM::protect(|| {
+    ///         point.with_borrow(|_: &Point| VM::raise(Class::from_existing("RuntimeError"), ""));
+    ///     });
+    ///
+    ///     assert!(result.is_err());
+    ///
+    ///     let sum = point.with_borrow(|point: &Point| point.x + point.y);
+    ///
+    ///     assert_eq!(sum, 3);
+    /// }
+    /// ```
+    fn with_borrow<T, F, R>(&self, func: F) -> R
+    where
+        T: TypedData,
+        F: FnOnce(&T) -> R,
+    {
+        let data = typed_data::borrow::<T>(self.value());
+
+        typed_data::with_guard(data, |data| func(&**data))
+    }
+
+    /// Mutably borrows the Rust structure which is wrapped into a Ruby object with `TypedData`
+    /// and passes it to the closure.
+    ///
+    /// Raises `TypeError` if the object does not wrap a `T` and `RuntimeError` if the data is
+    /// currently borrowed.
+    ///
+    /// The data is released before exceptions raised in the closure are propagated (and before
+    /// panics are resumed), so the closure can safely call Ruby code.
+    ///
+    /// See `TypedData` for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, TypedData, VM};
+    ///
+    /// pub struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// impl TypedData for Point {
+    ///     fn class() -> Class {
+    ///         Class::from_existing("Point")
+    ///     }
+    ///
+    ///     typed_data_type!(Point);
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Point", None);
+    ///
+    ///     let point = Point { x: 1, y: 2 }.wrap();
+    ///
+    ///     point.with_borrow_mut(|point: &mut Point| point.x = 10);
+    ///
+    ///     assert_eq!(point.with_borrow(|point: &Point| point.x), 10);
+    /// }
+    /// ```
+    fn with_borrow_mut<T, F, R>(&self, func: F) -> R
+    where
+        T: TypedData,
+        F: FnOnce(&mut T) -> R,
+    {
+        let data = typed_data::borrow_mut::<T>(self.value());
+
+        typed_data::with_guard(data, |data| func(&mut **data))
+    }
+
     /// Wraps calls to the object.
     ///
     /// Mostly used to have Ruby-like class definition DSL.
@@ -596,6 +869,143 @@ pub trait Object: From<Value> {
         AnyObject::from(result)
     }
 
+    /// Calls a given public method on an object similarly to Ruby `Object#public_send` method
+    ///
+    /// Raises `NoMethodError` if the method is private or protected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let array = Array::new().push(Fixnum::new(1));
+    ///
+    /// let length = array.public_send("length", None).try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(length, Ok(Fixnum::new(1)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// array = [1]
+    ///
+    /// array.public_send(:length) == 1
+    /// ```
+    fn public_send(&self, method: &str, arguments: Option<&[AnyObject]>) -> AnyObject {
+        let arguments = util::arguments_to_values(arguments);
+        let result = binding_util::call_public_method(self.value(), method, arguments);
+
+        AnyObject::from(result)
+    }
+
+    /// Calls a given method on an object passing the hash as keyword arguments
+    ///
+    /// Before Ruby 2.7 the hash is passed as the last argument, which is treated as keyword
+    /// arguments by these versions of Ruby.
+    ///
+    /// `rb_funcallv_kw()` is looked up at runtime with `dlsym()`, so on non-Unix systems with
+    /// Ruby 2.7 or newer the function raises `NotImplementedError` instead of passing the hash
+    /// as a positional argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Hash, Object, RString, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// VM::eval("def greeting(name, punctuation: '.'); \"Hello, #{name}#{punctuation}\"; end");
+    ///
+    /// let mut keywords = Hash::new();
+    ///
+    /// keywords.store(Symbol::new("punctuation"), RString::new("!"));
+    ///
+    /// let name = [RString::new("Rust").to_any_object()];
+    /// let greeting = VM::eval("self").send_kw("greeting", Some(&name), &keywords);
+    ///
+    /// assert_eq!(greeting.try_convert_to::<RString>().unwrap().to_str(), "Hello, Rust!");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// def greeting(name, punctuation: '.')
+    ///   "Hello, #{name}#{punctuation}"
+    /// end
+    ///
+    /// keywords = { punctuation: '!' }
+    ///
+    /// send(:greeting, 'Rust', **keywords) == 'Hello, Rust!'
+    /// ```
+    fn send_kw(&self, method: &str, arguments: Option<&[AnyObject]>, keywords: &Hash) -> AnyObject {
+        let mut arguments = util::arguments_to_values(arguments).unwrap_or_default();
+
+        arguments.push(keywords.value());
+
+        let result = binding_util::call_method_kw(self.value(), method, arguments);
+
+        AnyObject::from(result)
+    }
+
+    /// Calls a given method on an object and returns an exception raised by the method as `Err`
+    /// instead of raising it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Class, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let array = Array::new().push(Fixnum::new(1));
+    ///
+    /// let length = array.try_send("length", None).unwrap().try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(length, Ok(Fixnum::new(1)));
+    ///
+    /// let exception = array.try_send("something_else", None).unwrap_err();
+    ///
+    /// assert_eq!(exception.class(), Class::from_existing("NoMethodError"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// array = [1]
+    ///
+    /// array.send(:length) == 1
+    ///
+    /// begin
+    ///   array.send(:something_else)
+    /// rescue => exception
+    ///   exception.class == NoMethodError
+    /// end
+    /// ```
+    fn try_send(
+        &self,
+        method: &str,
+        arguments: Option<&[AnyObject]>,
+    ) -> ::std::result::Result<AnyObject, Exception> {
+        // Arguments are converted outside of `protect`, because the vector would not be freed
+        // if the method raises an exception
+        let arguments = util::arguments_to_values(arguments);
+        let method_id = binding_util::internal_id(method);
+        let receiver = self.value();
+        let mut result = None;
+
+        // The result is not set if the method raises an exception
+        let _ = vm::protect(|| {
+            let value = binding_util::call_method_with_id(receiver, method_id, &arguments);
+
+            result = Some(value);
+        });
+
+        match result {
+            Some(result) => Ok(AnyObject::from(result)),
+            None => Err(Exception::from(vm::take_errinfo())),
+        }
+    }
+
     /// Checks whether the object responds to given method
     ///
     /// # Examples
@@ -613,6 +1023,39 @@ pub trait Object: From<Value> {
         class::respond_to(self.value(), method)
     }
 
+    /// Returns an enumerator which calls the method with the arguments (Ruby `Object#to_enum`).
+    ///
+    /// See `return_enumerator!` for returning enumerators from methods called without a block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let array = Array::new().push(Fixnum::new(1)).push(Fixnum::new(2));
+    /// let enumerator = array.to_enum("each_slice", Some(&[Fixnum::new(1).to_any_object()]));
+    ///
+    /// let slices = enumerator.send("to_a", None).try_convert_to::<Array>().unwrap();
+    ///
+    /// assert_eq!(slices.length(), 2);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// array = [1, 2]
+    /// enumerator = array.to_enum(:each_slice, 1)
+    ///
+    /// enumerator.to_a == [[1], [2]]
+    /// ```
+    fn to_enum(&self, method: &str, arguments: Option<&[AnyObject]>) -> Enumerator {
+        let arguments = util::arguments_to_values(arguments);
+        let result = enumerator::enumeratorize(self.value(), method, arguments);
+
+        Enumerator::from(result)
+    }
+
     /// Checks whether the object is `nil`
     ///
     /// # Examples
@@ -820,6 +1263,40 @@ pub trait Object: From<Value> {
         AnyObject::from(result)
     }
 
+    /// Evaluates a string of Ruby code in the context of the object (Ruby `#instance_eval`).
+    ///
+    /// Instance variables and private methods of the object are accessible from the code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let mut object = RString::new("Hello");
+    ///
+    /// object.instance_variable_set("@count", Fixnum::new(1));
+    ///
+    /// let length = object.instance_eval("length + @count").try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(length, Ok(Fixnum::new(6)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// object = 'Hello'
+    ///
+    /// object.instance_variable_set(:@count, 1)
+    ///
+    /// object.instance_eval('length + @count') == 6
+    /// ```
+    fn instance_eval(&self, code: &str) -> AnyObject {
+        let result = class::instance_eval(self.value(), RString::new(code).value());
+
+        AnyObject::from(result)
+    }
+
     /// Returns the freeze status of the object.
     ///
     /// # Examples
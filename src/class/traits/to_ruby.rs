@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash as StdHash};
+
+use binding::fixnum;
+
+use {AnyObject, Array, Boolean, Float, Hash, NilClass, Object, RString};
+
+/// Conversion of native Rust values to Ruby objects
+///
+/// The trait is implemented for all Ruby objects (types which implement `Object`) and for the
+/// following native types:
+///
+///  - `i8`, `i16`, `i32`, `i64`, `isize`, `u8`, `u16`, `u32`, `u64`, `usize` as `Integer`;
+///  - `f32`, `f64` as `Float`;
+///  - `bool` as `true` or `false`;
+///  - `String`, `&str` as UTF-8 `String`;
+///  - `()` as `nil`;
+///  - `Option<T>` as `nil` or converted `T`;
+///  - `Vec<T>` and tuples as `Array`;
+///  - `HashMap<K, V>` as `Hash`.
+///
+/// Return values of methods defined with the `methods!` macro are converted using this trait,
+/// so the methods can return native Rust types directly.
+///
+/// # Examples
+///
+/// ```
+/// use ruru::{Array, Fixnum, Object, RString, ToRuby, VM};
+/// # VM::init();
+///
+/// let array = vec![1, 2, 3].to_ruby().try_convert_to::<Array>().unwrap();
+///
+/// assert_eq!(array.length(), 3);
+/// assert_eq!(array.at(0).try_convert_to::<Fixnum>(), Ok(Fixnum::new(1)));
+///
+/// let string = Some("Hello").to_ruby().try_convert_to::<RString>().unwrap();
+///
+/// assert_eq!(string.to_str(), "Hello");
+/// assert!(None::<i64>.to_ruby().is_nil());
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// array = [1, 2, 3]
+///
+/// array.length == 3
+/// array[0] == 1
+///
+/// string = 'Hello'
+/// ```
+pub trait ToRuby {
+    /// Converts the value to a Ruby object.
+    fn to_ruby(self) -> AnyObject;
+}
+
+impl<T: Object> ToRuby for T {
+    fn to_ruby(self) -> AnyObject {
+        self.to_any_object()
+    }
+}
+
+macro_rules! signed_to_ruby {
+    ($($native: ty),*) => {
+        $(
+            impl ToRuby for $native {
+                fn to_ruby(self) -> AnyObject {
+                    AnyObject::from(fixnum::i64_to_num(self as i64))
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! unsigned_to_ruby {
+    ($($native: ty),*) => {
+        $(
+            impl ToRuby for $native {
+                fn to_ruby(self) -> AnyObject {
+                    AnyObject::from(fixnum::u64_to_num(self as u64))
+                }
+            }
+        )*
+    }
+}
+
+signed_to_ruby!(i8, i16, i32, i64, isize);
+unsigned_to_ruby!(u8, u16, u32, u64, usize);
+
+impl ToRuby for f32 {
+    fn to_ruby(self) -> AnyObject {
+        Float::new(self as f64).to_any_object()
+    }
+}
+
+impl ToRuby for f64 {
+    fn to_ruby(self) -> AnyObject {
+        Float::new(self).to_any_object()
+    }
+}
+
+impl ToRuby for bool {
+    fn to_ruby(self) -> AnyObject {
+        Boolean::new(self).to_any_object()
+    }
+}
+
+impl ToRuby for &str {
+    fn to_ruby(self) -> AnyObject {
+        RString::new_utf8(self).to_any_object()
+    }
+}
+
+impl ToRuby for String {
+    fn to_ruby(self) -> AnyObject {
+        RString::new_utf8(&self).to_any_object()
+    }
+}
+
+impl ToRuby for () {
+    fn to_ruby(self) -> AnyObject {
+        NilClass::new().to_any_object()
+    }
+}
+
+impl<T: ToRuby> ToRuby for Option<T> {
+    fn to_ruby(self) -> AnyObject {
+        match self {
+            Some(value) => value.to_ruby(),
+            None => NilClass::new().to_any_object(),
+        }
+    }
+}
+
+impl<T: ToRuby> ToRuby for Vec<T> {
+    fn to_ruby(self) -> AnyObject {
+        let mut array = Array::with_capacity(self.len());
+
+        for item in self {
+            array.push(item.to_ruby());
+        }
+
+        array.to_any_object()
+    }
+}
+
+impl<K, V, S> ToRuby for HashMap<K, V, S>
+where
+    K: ToRuby + Eq + StdHash,
+    V: ToRuby,
+    S: BuildHasher,
+{
+    fn to_ruby(self) -> AnyObject {
+        let mut hash = Hash::new();
+
+        for (key, value) in self {
+            hash.store(key.to_ruby(), value.to_ruby());
+        }
+
+        hash.to_any_object()
+    }
+}
+
+macro_rules! tuple_to_ruby {
+    ($length: expr; $($name: ident),+) => {
+        impl<$($name: ToRuby),+> ToRuby for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn to_ruby(self) -> AnyObject {
+                let ($($name,)+) = self;
+                let mut array = Array::with_capacity($length);
+
+                $(array.push($name.to_ruby());)+
+
+                array.to_any_object()
+            }
+        }
+    }
+}
+
+tuple_to_ruby!(1; A);
+tuple_to_ruby!(2; A, B);
+tuple_to_ruby!(3; A, B, C);
+tuple_to_ruby!(4; A, B, C, D);
+tuple_to_ruby!(5; A, B, C, D, E);
+tuple_to_ruby!(6; A, B, C, D, E, F);
+tuple_to_ruby!(7; A, B, C, D, E, F, G);
+tuple_to_ruby!(8; A, B, C, D, E, F, G, H);
@@ -0,0 +1,424 @@
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use binding::{class, fiber, vm};
+use types::{c_char, c_void, size_t, DataType, DataTypeFunction, Value};
+
+use {AnyObject, Class, Object};
+
+/// Rust structures which can be wrapped into Ruby objects
+///
+/// Unlike `wrappable_struct!`, the trait does not require any static wrappers to be passed
+/// around. The type declares its Ruby class and, optionally, how to mark Ruby objects it
+/// contains and how much memory it uses. Its Ruby data type is generated by `typed_data_type!`
+/// inside the implementation.
+///
+/// The wrapped data is accessed with `Object::with_borrow()` and `Object::with_borrow_mut()`.
+/// They check that the object wraps the requested type (raising `TypeError` otherwise) and that
+/// the data is not borrowed mutably elsewhere (raising `RuntimeError` otherwise), so the wrapped
+/// data is never aliased by mutable references.
+///
+/// If the type implements `Default`, `Class::define_allocator()` allows to create objects of the
+/// class with `Class#new` and `Class#allocate` and to set up the data in `initialize`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate ruru;
+///
+/// use ruru::{Class, Fixnum, NilClass, Object, TypedData, VM};
+///
+/// #[derive(Default)]
+/// pub struct Counter {
+///     count: i64,
+/// }
+///
+/// impl TypedData for Counter {
+///     fn class() -> Class {
+///         Class::from_existing("Counter")
+///     }
+///
+///     typed_data_type!(Counter);
+/// }
+///
+/// class!(RubyCounter);
+///
+/// methods!(
+///     RubyCounter,
+///     itself,
+///
+///     fn counter_initialize(start: Fixnum) -> NilClass {
+///         let start = start.map(|start| start.to_i64()).unwrap_or(0);
+///
+///         itself.with_borrow_mut(|counter: &mut Counter| counter.count = start);
+///
+///         NilClass::new()
+///     }
+///
+///     fn counter_increment() -> i64 {
+///         itself.with_borrow_mut(|counter: &mut Counter| {
+///             counter.count += 1;
+///             counter.count
+///         })
+///     }
+///
+///     fn counter_count() -> i64 {
+///         itself.with_borrow(|counter: &Counter| counter.count)
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     Class::new("Counter", None).define(|itself| {
+///         itself.define_allocator::<Counter>();
+///
+///         itself.def("initialize", counter_initialize);
+///         itself.def("increment", counter_increment);
+///         itself.def("count", counter_count);
+///     });
+///
+///     // Wrap a Rust value directly
+///     let counter = Counter { count: 10 }.wrap();
+///
+///     assert_eq!(counter.with_borrow(|counter: &Counter| counter.count), 10);
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// counter = Counter.new(1)
+///
+/// counter.increment
+/// counter.count == 2
+/// ```
+pub trait TypedData: Sized + 'static {
+    /// Returns the Ruby class of objects which wrap the type.
+    fn class() -> Class;
+
+    /// Returns the Ruby data type which describes the wrapped struct.
+    ///
+    /// The data type is created once for every type at compile time. The function should be
+    /// generated with `typed_data_type!` macro (`#[derive(RubyClass)]` generates it
+    /// automatically).
+    fn data_type() -> &'static DataType;
+
+    /// Marks Ruby objects contained in the struct to prevent their garbage collection.
+    ///
+    /// It must be implemented if the struct contains any Ruby objects, which should be marked
+    /// with `GC::mark()`. It is not allowed to allocate new Ruby objects in this function.
+    fn mark(&self) {}
+
+    /// Returns the size of memory (in bytes) used by the struct.
+    ///
+    /// The size is reported by `ObjectSpace.memsize_of`.
+    fn size(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+
+    /// Wraps the value into a new instance of the class returned by `class()`.
+    fn wrap(self) -> AnyObject {
+        let data = Box::into_raw(Box::new(DataCell::new(self))) as *mut c_void;
+        let value = class::wrap_typed_data(Self::class().value(), data, Self::data_type());
+
+        AnyObject::from(value)
+    }
+}
+
+/// Ruby data type of a `TypedData` struct, which is stored in a `static`
+///
+/// It is created by `typed_data_type!` macro.
+pub struct StaticDataType {
+    data_type: DataType,
+}
+
+// The data type is never changed after it is created
+unsafe impl Sync for StaticDataType {}
+
+impl StaticDataType {
+    /// Creates a data type with the name, which must end with `\0`.
+    pub const fn new<T: TypedData>(name: &'static str) -> Self {
+        StaticDataType {
+            data_type: DataType {
+                wrap_struct_name: name.as_ptr() as *const c_char,
+                parent: ptr::null(),
+                data: ptr::null_mut(),
+                flags: Value { value: 0 },
+
+                function: DataTypeFunction {
+                    dmark: Some(mark::<T>),
+                    dfree: Some(free::<T>),
+                    dsize: Some(size::<T>),
+                    reserved: [ptr::null_mut(); 2],
+                },
+            },
+        }
+    }
+
+    pub fn get(&'static self) -> &'static DataType {
+        &self.data_type
+    }
+}
+
+/// An immutable borrow of data wrapped with `TypedData` (see `Object::borrow()`)
+pub struct Ref<'a, T: 'a> {
+    value: &'a T,
+    _guard: BorrowGuard,
+}
+
+impl<'a, T> Deref for Ref<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+/// A mutable borrow of data wrapped with `TypedData` (see `Object::borrow_mut()`)
+pub struct RefMut<'a, T: 'a> {
+    value: &'a mut T,
+    _guard: BorrowGuard,
+}
+
+impl<'a, T> Deref for RefMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T> DerefMut for RefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+// Wrapped data with a borrow flag like in `RefCell`. The flag is the number of immutable
+// borrows or `-1` if the data is borrowed mutably.
+struct DataCell<T> {
+    borrow: Cell<isize>,
+    value: UnsafeCell<T>,
+}
+
+impl<T> DataCell<T> {
+    fn new(value: T) -> Self {
+        DataCell {
+            borrow: Cell::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+}
+
+// Borrows which are alive in the current thread.
+//
+// Ruby exceptions skip destructors of Rust values, so borrow guards which are skipped by an
+// exception are never dropped. Their borrows are released by `vm::protect()` when it catches
+// the exception (see `release_borrows()`). Borrows are registered for each fiber separately,
+// because suspended fibers can still hold their borrows.
+thread_local! {
+    static BORROWS: RefCell<Borrows> = const {
+        RefCell::new(Borrows {
+            next_id: 0,
+            active: Vec::new(),
+        })
+    };
+}
+
+struct Borrows {
+    next_id: usize,
+    active: Vec<ActiveBorrow>,
+}
+
+struct ActiveBorrow {
+    id: usize,
+    fiber: Value,
+    flag: *const Cell<isize>,
+}
+
+// Releases the borrow when it is dropped, unless the borrow has already been released by
+// `release_borrows()`
+struct BorrowGuard {
+    id: usize,
+}
+
+impl BorrowGuard {
+    fn new(flag: &Cell<isize>) -> Self {
+        let fiber = fiber::current();
+
+        BORROWS.with(|borrows| {
+            let mut borrows = borrows.borrow_mut();
+            let id = borrows.next_id;
+
+            borrows.next_id += 1;
+            borrows.active.push(ActiveBorrow {
+                id: id,
+                fiber: fiber,
+                flag: flag,
+            });
+
+            BorrowGuard { id: id }
+        })
+    }
+}
+
+impl Drop for BorrowGuard {
+    fn drop(&mut self) {
+        BORROWS.with(|borrows| {
+            let mut borrows = borrows.borrow_mut();
+            let position = borrows
+                .active
+                .iter()
+                .rposition(|borrow| borrow.id == self.id);
+
+            if let Some(position) = position {
+                release(&borrows.active.remove(position));
+            }
+        });
+    }
+}
+
+fn release(borrow: &ActiveBorrow) {
+    let flag = unsafe { &*borrow.flag };
+
+    if flag.get() < 0 {
+        flag.set(0);
+    } else {
+        flag.set(flag.get() - 1);
+    }
+}
+
+// Returns a mark which is passed to `release_borrows()`
+pub fn borrows_mark() -> usize {
+    BORROWS.with(|borrows| borrows.borrow().next_id)
+}
+
+// Releases borrows of the current fiber which have been made after the mark was taken
+pub fn release_borrows(mark: usize) {
+    let has_borrows = BORROWS.with(|borrows| {
+        let borrows = borrows.borrow();
+
+        borrows
+            .active
+            .last()
+            .is_some_and(|borrow| borrow.id >= mark)
+    });
+
+    if !has_borrows {
+        return;
+    }
+
+    let fiber = fiber::current();
+
+    BORROWS.with(|borrows| {
+        borrows.borrow_mut().active.retain(|borrow| {
+            if borrow.id < mark || borrow.fiber != fiber {
+                return true;
+            }
+
+            release(borrow);
+
+            false
+        });
+    });
+}
+
+fn cell<'a, T: TypedData>(object: Value) -> &'a DataCell<T> {
+    let data = class::check_typed_data(object, T::data_type());
+
+    unsafe { &*(data as *const DataCell<T>) }
+}
+
+pub fn borrow<'a, T: TypedData>(object: Value) -> Ref<'a, T> {
+    let cell = cell::<T>(object);
+    let borrow = cell.borrow.get();
+
+    if borrow < 0 {
+        raise_borrow_error("Wrapped data is already borrowed mutably");
+    }
+
+    cell.borrow.set(borrow + 1);
+
+    Ref {
+        value: unsafe { &*cell.value.get() },
+        _guard: BorrowGuard::new(&cell.borrow),
+    }
+}
+
+pub fn borrow_mut<'a, T: TypedData>(object: Value) -> RefMut<'a, T> {
+    let cell = cell::<T>(object);
+
+    if cell.borrow.get() != 0 {
+        raise_borrow_error("Wrapped data is already borrowed");
+    }
+
+    cell.borrow.set(-1);
+
+    RefMut {
+        value: unsafe { &mut *cell.value.get() },
+        _guard: BorrowGuard::new(&cell.borrow),
+    }
+}
+
+// Runs the closure while the data is borrowed by `guard`. Exceptions and panics are propagated
+// only after the guard is dropped, otherwise the data would stay borrowed forever.
+pub fn with_guard<G, F, R>(mut guard: G, func: F) -> R
+where
+    F: FnOnce(&mut G) -> R,
+{
+    let mut result = None;
+
+    let state = vm::protect(|| {
+        result = Some(panic::catch_unwind(AssertUnwindSafe(|| func(&mut guard))));
+    });
+
+    drop(guard);
+
+    match (result, state) {
+        (Some(Ok(result)), _) => result,
+        (Some(Err(payload)), _) => panic::resume_unwind(payload),
+        (None, Err(state)) => vm::jump_tag(state),
+        (None, Ok(_)) => unreachable!(),
+    }
+}
+
+fn raise_borrow_error(message: &str) -> ! {
+    vm::raise(Class::from_existing("RuntimeError").value(), message);
+
+    unreachable!()
+}
+
+pub extern "C" fn allocate<T: TypedData + Default>(klass: Value) -> Value {
+    let data = Box::into_raw(Box::new(DataCell::new(T::default()))) as *mut c_void;
+
+    class::wrap_typed_data(klass, data, T::data_type())
+}
+
+// The data is accessed without checking the borrow state, because garbage collection can start
+// while the data is borrowed mutably (for example, when a new object is allocated in a method
+// which holds `RefMut`). Skipping marking in this case would lead to collection of live objects.
+extern "C" fn mark<T: TypedData>(data: *mut c_void) {
+    let cell = unsafe { &*(data as *const DataCell<T>) };
+
+    unsafe { (*cell.value.get()).mark() };
+}
+
+// The object can be collected while its data is still borrowed by a guard which has been
+// skipped by an exception, but not released by `vm::protect()` yet. The memory is leaked in
+// this case, because the borrow flag is reset later.
+extern "C" fn free<T: TypedData>(data: *mut c_void) {
+    let cell = unsafe { Box::from_raw(data as *mut DataCell<T>) };
+
+    if cell.borrow.get() != 0 {
+        mem::forget(cell);
+    }
+}
+
+extern "C" fn size<T: TypedData>(data: *const c_void) -> size_t {
+    let cell = unsafe { &*(data as *const DataCell<T>) };
+
+    unsafe { (*cell.value.get()).size() as size_t }
+}
@@ -1,9 +1,9 @@
 use std::slice;
 
 use binding::vm;
-use types::{Argc, Value};
+use types::{Argc, Value, VariableGetter, VariableSetter};
 
-use {AnyObject, Class, Object, Proc};
+use {AnyObject, Class, Exception, Object, Proc};
 
 /// Virtual Machine and helpers
 pub struct VM;
@@ -59,6 +59,68 @@ impl VM {
         vm::require(name);
     }
 
+    /// Evaluates a string of Ruby code and returns the result.
+    ///
+    /// Exceptions raised by the code are propagated to the caller. To rescue them in Rust, use
+    /// `VM::eval_protected()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let result = VM::eval("[1, 2, 3].inject(:+)").try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(result, Ok(Fixnum::new(6)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// eval('[1, 2, 3].inject(:+)') == 6
+    /// ```
+    pub fn eval(code: &str) -> AnyObject {
+        let result = vm::eval_string(code);
+
+        AnyObject::from(result)
+    }
+
+    /// Evaluates a string of Ruby code and returns the result or the exception raised by the
+    /// code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let result = VM::eval_protected("2 + 2").map(|result| result.try_convert_to::<Fixnum>());
+    ///
+    /// assert_eq!(result, Ok(Ok(Fixnum::new(4))));
+    ///
+    /// let exception = VM::eval_protected("raise ArgumentError, 'Wrong argument'").unwrap_err();
+    ///
+    /// assert_eq!(exception.class(), Class::from_existing("ArgumentError"));
+    /// assert_eq!(exception.message(), "Wrong argument");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// begin
+    ///   eval("raise ArgumentError, 'Wrong argument'")
+    /// rescue => exception
+    ///   exception.class == ArgumentError
+    ///   exception.message == 'Wrong argument'
+    /// end
+    /// ```
+    pub fn eval_protected(code: &str) -> Result<AnyObject, Exception> {
+        vm::eval_string_protect(code)
+            .map(AnyObject::from)
+            .map_err(|_| Exception::from(vm::take_errinfo()))
+    }
+
     /// Raises an exception.
     ///
     /// # Examples
@@ -286,8 +348,9 @@ impl VM {
     #[deprecated(since = "0.9.2", note = "Use `Thread::call_without_gvl()` instead")]
     pub fn thread_call_without_gvl<F, R, G>(func: F, unblock_func: Option<G>) -> R
     where
-        F: FnOnce() -> R,
-        G: FnOnce(),
+        F: Send + FnOnce() -> R,
+        G: Send + FnOnce(),
+        R: Send,
     {
         vm::thread_call_without_gvl(func, unblock_func)
     }
@@ -295,8 +358,9 @@ impl VM {
     #[deprecated(since = "0.9.2", note = "Use `Thread::call_without_gvl2()` instead")]
     pub fn thread_call_without_gvl2<F, R, G>(func: F, unblock_func: Option<G>) -> R
     where
-        F: FnOnce() -> R,
-        G: FnOnce(),
+        F: Send + FnOnce() -> R,
+        G: Send + FnOnce(),
+        R: Send,
     {
         vm::thread_call_without_gvl2(func, unblock_func)
     }
@@ -315,4 +379,152 @@ impl VM {
     {
         vm::protect(func)
     }
+
+    /// Returns the value of a global variable.
+    ///
+    /// The name can be passed with or without the leading `$`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Boolean, Object, VM};
+    /// # VM::init();
+    ///
+    /// let verbose = VM::global_get("$VERBOSE");
+    ///
+    /// if verbose.try_convert_to::<Boolean>().map(|verbose| verbose.to_bool()) == Ok(true) {
+    ///     println!("Running in verbose mode");
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// puts 'Running in verbose mode' if $VERBOSE
+    /// ```
+    pub fn global_get(name: &str) -> AnyObject {
+        let result = vm::global_get(name);
+
+        AnyObject::from(result)
+    }
+
+    /// Sets the value of a global variable and returns it.
+    ///
+    /// The name can be passed with or without the leading `$`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// VM::global_set("$counter", &Fixnum::new(1));
+    ///
+    /// let counter = VM::global_get("$counter").try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(counter, Ok(Fixnum::new(1)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// $counter = 1
+    ///
+    /// $counter == 1
+    /// ```
+    pub fn global_set<T: Object>(name: &str, value: &T) -> AnyObject {
+        let result = vm::global_set(name, value.value());
+
+        AnyObject::from(result)
+    }
+
+    /// Defines a global variable backed by Rust functions.
+    ///
+    /// `getter` is called every time the variable is read and `setter` is called with the new
+    /// value every time the variable is assigned. The functions also receive the ID of the
+    /// variable and a pointer which are used by Ruby internally and can be ignored.
+    ///
+    /// Any object can be assigned to the variable, so `setter` receives `AnyObject` and should
+    /// check its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    /// #[macro_use] extern crate lazy_static;
+    ///
+    /// use std::sync::Mutex;
+    ///
+    /// use ruru::types::{c_void, Id};
+    /// use ruru::{AnyObject, Class, Object, RString, VM};
+    ///
+    /// lazy_static! {
+    ///     static ref CONFIG: Mutex<String> = Mutex::new("default".to_string());
+    /// }
+    ///
+    /// extern "C" fn get_config(_id: Id, _data: *mut c_void) -> RString {
+    ///     RString::new(&CONFIG.lock().unwrap())
+    /// }
+    ///
+    /// extern "C" fn set_config(value: AnyObject, _id: Id, _data: *mut c_void) {
+    ///     match value.try_convert_to::<RString>() {
+    ///         Ok(value) => *CONFIG.lock().unwrap() = value.to_string(),
+    ///         Err(_) => VM::raise(Class::from_existing("TypeError"), "config must be a String"),
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     VM::define_virtual_variable("$mygem_config", get_config, set_config);
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// $mygem_config = 'custom'
+    ///
+    /// $mygem_config == 'custom'
+    ///
+    /// $mygem_config = 1 # raises TypeError
+    /// ```
+    pub fn define_virtual_variable<O: Object>(
+        name: &str,
+        getter: VariableGetter<O>,
+        setter: VariableSetter,
+    ) {
+        vm::define_virtual_variable(name, getter, Some(setter));
+    }
+
+    /// Defines a read-only global variable backed by a Rust function.
+    ///
+    /// `getter` is called every time the variable is read. Assigning the variable raises
+    /// `NameError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::types::{c_void, Id};
+    /// use ruru::{RString, VM};
+    ///
+    /// extern "C" fn get_version(_id: Id, _data: *mut c_void) -> RString {
+    ///     RString::new("1.0.0")
+    /// }
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     VM::define_readonly_virtual_variable("$mygem_version", get_version);
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// $mygem_version == '1.0.0'
+    ///
+    /// $mygem_version = '2.0.0' # raises NameError
+    /// ```
+    pub fn define_readonly_virtual_variable<O: Object>(name: &str, getter: VariableGetter<O>) {
+        vm::define_virtual_variable(name, getter, None);
+    }
 }
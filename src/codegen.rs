@@ -0,0 +1,108 @@
+//! Helpers for the code generated by macros of ruru and `ruru-derive`.
+//!
+//! The module is not a part of the public API and can be changed at any time.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use binding::vm;
+use result::Error;
+use types::Argc;
+use util;
+
+use {AnyObject, Class, Exception, FromRuby, Object, VM};
+
+pub use class::traits::typed_data::StaticDataType;
+
+/// Parses arguments of a method and raises `ArgumentError` if their number is not `expected`.
+pub fn parse_arguments(argc: Argc, argv: *const AnyObject, expected: usize) -> Vec<AnyObject> {
+    let arguments = VM::parse_arguments(argc, argv);
+
+    if arguments.len() != expected {
+        let message = format!(
+            "wrong number of arguments (given {}, expected {})",
+            arguments.len(),
+            expected
+        );
+
+        VM::raise(Class::from_existing("ArgumentError"), &message);
+    }
+
+    arguments
+}
+
+/// Converts an argument of a method and raises an exception if the conversion fails.
+pub fn convert<T: FromRuby>(argument: &AnyObject) -> T {
+    match T::from_ruby(argument) {
+        Ok(value) => value,
+        Err(error) => raise(&error),
+    }
+}
+
+/// Raises the error as a Ruby exception.
+pub fn raise(error: &Error) -> ! {
+    VM::raise(error.to_exception(), &error.to_string());
+
+    unreachable!()
+}
+
+/// Runs a method generated by `#[ruby_methods]`.
+///
+/// Panics cannot unwind into Ruby, so they are caught and raised as `RuntimeError`. Ruby
+/// exceptions are propagated.
+pub fn method<F: FnOnce() -> AnyObject>(func: F) -> AnyObject {
+    let mut result = None;
+
+    let state = vm::protect(|| {
+        result = Some(panic::catch_unwind(AssertUnwindSafe(func)));
+    });
+
+    match (result, state) {
+        (Some(Ok(result)), _) => result,
+        (Some(Err(payload)), _) => raise_panic(payload),
+        (None, Err(state)) => vm::jump_tag(state),
+        (None, Ok(_)) => unreachable!(),
+    }
+}
+
+/// Raises a caught panic as `RuntimeError`.
+pub fn raise_panic(payload: Box<Any + Send>) -> ! {
+    let message = format!("panicked at '{}'", util::panic_payload_to_string(&*payload));
+    let exception = Exception::new(&Class::from_existing("RuntimeError"), Some(&message));
+
+    // Ruby longjmps out of the function, so nothing is dropped after the raise
+    drop(message);
+    drop(payload);
+
+    vm::raise_exception(exception.value())
+}
+
+/// Runs initialization of the extension `name` generated by `init!`.
+///
+/// Ruby exceptions and panics are caught and raised again as `LoadError`.
+pub fn init<F: FnOnce()>(name: &str, ractor_safe: bool, func: F) {
+    let mut panic_message = None;
+
+    if ractor_safe {
+        vm::ext_ractor_safe(true);
+    }
+
+    let result = VM::protect(|| {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(func)) {
+            panic_message = Some(format!(
+                "panicked at '{}'",
+                util::panic_payload_to_string(&*payload)
+            ));
+        }
+    });
+
+    let reason = match (result, panic_message) {
+        (_, Some(message)) => message,
+        (Err(_), None) => Exception::from(vm::take_errinfo()).inspect(),
+        (Ok(_), None) => return,
+    };
+
+    let message = format!("Failed to initialize {}: {}", name, reason);
+
+    VM::raise(Class::from_existing("LoadError"), &message);
+}
@@ -64,11 +64,15 @@ macro_rules! class {
         #[derive(Debug, PartialEq)]
         pub struct $class {
             value: $crate::types::Value,
+            _marker: $crate::types::NotSend,
         }
 
         impl From<$crate::types::Value> for $class {
             fn from(value: $crate::types::Value) -> Self {
-                $class { value: value }
+                $class {
+                    value: value,
+                    _marker: ::std::marker::PhantomData,
+                }
             }
         }
 
@@ -78,7 +82,7 @@ macro_rules! class {
                 self.value
             }
         }
-    }
+    };
 }
 
 /// Creates unsafe callbacks for Ruby methods
@@ -179,15 +183,25 @@ macro_rules! unsafe_methods {
 ///
 /// Unlike `unsafe_methods!`, this macro is safe, because:
 ///
-///  - it uses safe conversions of arguments (`Object::try_convert_to()`);
+///  - it uses safe conversions of arguments (`FromRuby::from_ruby()`);
 ///  - it checks if arguments are present;
 ///
-/// Each argument will have type `Result<Object, Error>`.
+/// Each argument will have type `Result<T, Error>`.
 ///
 /// For example, if you declare `number: Fixnum` in the method definition, it will have actual
 /// type `number: Result<Fixnum, Error>`.
 ///
-/// See examples below and docs for `Object::try_convert_to()` for more information.
+/// Arguments can be declared with any type which implements `FromRuby`. Besides Ruby objects
+/// it includes native Rust types like `i64`, `String`, `Vec<T>` or `Option<T>`.
+///
+/// The return type can be any type which implements `ToRuby`. The returned value is converted
+/// to a Ruby object automatically.
+///
+/// Panics in the body of a method are raised as `RuntimeError`, because they cannot unwind
+/// into Ruby.
+///
+/// See examples below and docs for `FromRuby`, `ToRuby` and `Object::try_convert_to()` for
+/// more information.
 ///
 /// # Examples
 ///
@@ -269,6 +283,54 @@ macro_rules! unsafe_methods {
 ///   end
 /// end
 /// ```
+///
+/// ## Native types
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Class, Object, VM};
+///
+/// class!(Calculator);
+///
+/// methods!(
+///     Calculator,
+///     itself,
+///
+///     fn sum(numbers: Vec<i64>) -> i64 {
+///         numbers.map(|numbers| numbers.iter().sum()).unwrap_or(0)
+///     }
+///
+///     fn divide(a: f64, b: f64) -> Option<f64> {
+///         let (a, b) = (a.unwrap(), b.unwrap());
+///
+///         if b == 0.0 { None } else { Some(a / b) }
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     Class::new("Calculator", None).define(|itself| {
+///         itself.def("sum", sum);
+///         itself.def("divide", divide);
+///     });
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class Calculator
+///   def sum(numbers)
+///     numbers.is_a?(Array) ? numbers.inject(0, :+) : 0
+///   end
+///
+///   def divide(a, b)
+///     a / b unless b.zero?
+///   end
+/// end
+/// ```
 #[macro_export]
 macro_rules! methods {
     (
@@ -276,61 +338,163 @@ macro_rules! methods {
         $itself_name: ident,
         $(
             fn $method_name: ident
-            ($($arg_name: ident: $arg_type: ty),*) -> $return_type: ident $body: block
+            ($($arg_name: ident: $arg_type: ty),*) -> $return_type: ty $body: block
         )*
     ) => {
         $(
-            #[allow(unused_mut)]
             pub extern fn $method_name(argc: $crate::types::Argc,
                                        argv: *const $crate::AnyObject,
-                                       mut $itself_name: $itself_class) -> $return_type {
-                let _arguments = $crate::VM::parse_arguments(argc, argv);
-                let mut _i = 0;
+                                       itself: $itself_class) -> $crate::AnyObject {
+                // The body is a separate function, so `return` in the body returns its value
+                #[allow(unused_mut)]
+                fn body(
+                    mut $itself_name: $itself_class,
+                    $($arg_name: $crate::result::Result<$arg_type>),*
+                ) -> $return_type $body
 
-                $(
-                    let $arg_name =
-                        _arguments
-                            .get(_i)
-                            .ok_or_else(|| {
-                                $crate::result::Error::ArgumentError(
-                                    format!(
-                                        "Argument '{}: {}' not found for method '{}'",
-                                        stringify!($arg_name),
-                                        stringify!($arg_type),
-                                        stringify!($method_name)
+                $crate::codegen::method(|| {
+                    let _arguments = $crate::VM::parse_arguments(argc, argv);
+                    let mut _i = 0;
+
+                    $(
+                        let $arg_name =
+                            _arguments
+                                .get(_i)
+                                .ok_or_else(|| {
+                                    $crate::result::Error::ArgumentError(
+                                        format!(
+                                            "Argument '{}: {}' not found for method '{}'",
+                                            stringify!($arg_name),
+                                            stringify!($arg_type),
+                                            stringify!($method_name)
+                                        )
                                     )
-                                )
-                            }).and_then(|argument| {
-                                <$crate::AnyObject as $crate::Object>
-                                    ::try_convert_to::<$arg_type>(argument)
-                            });
+                                }).and_then(|argument| {
+                                    <$arg_type as $crate::FromRuby>::from_ruby(argument)
+                                });
 
-                    _i += 1;
-                )*
+                        _i += 1;
+                    )*
 
-                $body
+                    $crate::ToRuby::to_ruby(body(itself, $($arg_name),*))
+                })
             }
         )*
     }
 }
 
-/// Makes a Rust struct wrappable for Ruby objects.
+/// Returns an enumerator from a method if it is called without a block.
+///
+/// The macro is used in methods which yield values to a block. If no block is given, the method
+/// returns `itself.to_enum("method", arguments)` immediately (like `RETURN_ENUMERATOR` in C
+/// extensions), so it behaves like `Array#each` or `IO#each_line` in Ruby. The return type of
+/// the method must be `AnyObject`.
 ///
-/// **Note:** Currently to be able to use `wrappable_struct!` macro, you should include
-/// `lazy_static` crate to the crate you are working on.
+/// The arguments are passed as `Option<&[AnyObject]>` and they are passed to the method again
+/// when the enumerator is used.
 ///
-/// `Cargo.toml`
+/// # Examples
 ///
-/// ```toml
-/// lazy_static = "0.2.1" # the version is not a strict requirement
 /// ```
+/// #[macro_use] extern crate ruru;
 ///
-/// Crate root `lib.rs` or `main.rs`
+/// use ruru::{AnyObject, Array, Class, Fixnum, Object, RString, VM};
 ///
-/// ```ignore
-/// #[macro_use]
-/// extern crate lazy_static;
+/// methods!(
+///     RString,
+///     itself,
+///
+///     fn string_each_word() -> AnyObject {
+///         return_enumerator!(itself, "each_word");
+///
+///         let block = VM::block_proc();
+///
+///         for word in itself.to_string().split_whitespace() {
+///             block.call(Some(&[RString::new(word).to_any_object()]));
+///         }
+///
+///         itself.to_any_object()
+///     }
+///
+///     fn string_each_char_of(index: Fixnum) -> AnyObject {
+///         let index_argument = [index.as_ref().unwrap().to_any_object()];
+///
+///         return_enumerator!(itself, "each_char_of", Some(&index_argument));
+///
+///         let index = index.unwrap().to_i64() as usize;
+///         let block = VM::block_proc();
+///
+///         for word in itself.to_string().split_whitespace() {
+///             if let Some(character) = word.chars().nth(index) {
+///                 block.call(Some(&[RString::new(&character.to_string()).to_any_object()]));
+///             }
+///         }
+///
+///         itself.to_any_object()
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     Class::from_existing("String").define(|itself| {
+///         itself.def("each_word", string_each_word);
+///         itself.def("each_char_of", string_each_char_of);
+///     });
+///
+///     let string = RString::new("Hello from Rust");
+///
+///     let words = string.send("each_word", None).send("to_a", None);
+///     let words = words.try_convert_to::<Array>().unwrap();
+///
+///     assert_eq!(words.length(), 3);
+///
+///     let characters = string.send("each_char_of", Some(&[Fixnum::new(1).to_any_object()]));
+///     let characters = characters.send("to_a", None).try_convert_to::<Array>().unwrap();
+///
+///     assert_eq!(characters.length(), 3);
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class String
+///   def each_word
+///     return to_enum(:each_word) unless block_given?
+///
+///     split.each { |word| yield word }
+///
+///     self
+///   end
+///
+///   def each_char_of(index)
+///     return to_enum(:each_char_of, index) unless block_given?
+///
+///     split.each { |word| yield word[index] if word[index] }
+///
+///     self
+///   end
+/// end
+///
+/// 'Hello from Rust'.each_word.to_a == ['Hello', 'from', 'Rust']
+/// 'Hello from Rust'.each_char_of(1).to_a == ['e', 'r', 'u']
 /// ```
+#[macro_export]
+macro_rules! return_enumerator {
+    ($itself: expr, $method: expr) => {
+        return_enumerator!($itself, $method, None);
+    };
+
+    ($itself: expr, $method: expr, $arguments: expr) => {
+        if !$crate::VM::is_block_given() {
+            let enumerator = $crate::Object::to_enum(&$itself, $method, $arguments);
+
+            return $crate::Object::to_any_object(&enumerator);
+        }
+    };
+}
+
+/// Makes a Rust struct wrappable for Ruby objects.
 ///
 /// # Arguments
 ///
@@ -353,8 +517,8 @@ macro_rules! methods {
 ///     For example,
 ///
 ///     ```ignore
-///     server1.get_data(&*SERVER_WRAPPER);
-///     server2.get_data(&*SERVER_WRAPPER); // <-- the same `SERVER_WRAPPER`
+///     server1.get_data(&SERVER_WRAPPER);
+///     server2.get_data(&SERVER_WRAPPER); // <-- the same `SERVER_WRAPPER`
 ///     ```
 ///
 ///  - (optional) `mark(data) { ... }` is a block which will be called during the "mark"
@@ -373,6 +537,45 @@ macro_rules! methods {
 ///
 ///      - It is not allowed to allocate new Ruby objects in the `mark` function.
 ///
+///  - (optional) `size(data) { ... }` is a block which returns the size of memory (in bytes)
+///    used by the wrapped struct.
+///
+///    The size is reported by `ObjectSpace.memsize_of` and used by memory profilers.
+///    If the block is not given, `mem::size_of::<$struct_name>()` is used, which does not
+///    include memory allocated on the heap by the struct (e.g. contents of `Vec`s).
+///
+///    `data` argument will be yielded as a reference to the wrapped struct (`&$struct_name`).
+///
+///    To make the garbage collector take large heap allocations into account, report them
+///    with `GC::adjust_memory_usage()` as well.
+///
+///  - (optional) `parent(PARENT_WRAPPER)` makes the struct a child of the struct wrapped by
+///    `PARENT_WRAPPER` (a `$static_name` of another `wrappable_struct!`).
+///
+///    The struct must implement `AsMut<Parent>`. Objects wrapping the struct can be passed to
+///    `get_data()` with the parent wrapper, which returns the result of `as_mut()`. It allows
+///    to share methods between Ruby classes wrapping a hierarchy of structs.
+///
+///    See `Object::is_typed_data()` for an example.
+///
+///  - (optional) `clone` makes the wrapped data copyable by Ruby `dup` and `clone`.
+///
+///    The struct must implement `Clone`. `Class::wrap_data()` defines the copy functions for
+///    the class of the objects when the data is wrapped (see `Class::define_copy()`). Without
+///    the option, `dup` and `clone` of the objects raise `TypeError`.
+///
+///  - (optional) `marshal` makes the wrapped data dumpable by Ruby `Marshal`.
+///
+///    The struct must implement `MarshalData`. The macro implements `MarshalDataTypeWrapper`
+///    for the wrapper, which should be passed to `Class::define_marshal()` for the class
+///    of the objects.
+///
+///  - (optional) `class("ClassName")` restricts the classes of the objects which wrap
+///    the struct.
+///
+///    `Class::wrap_data()` raises `TypeError` if the class is neither `ClassName` nor its
+///    subclass, so the data cannot be wrapped into objects of unrelated classes by mistake.
+///
 /// The result of `wrappable_struct!` is:
 ///
 /// ```ignore
@@ -384,7 +587,7 @@ macro_rules! methods {
 ///     // ...
 /// }
 ///
-/// pub static ref SERVER_WRAPPER: ServerWrapper<Server> = // ...
+/// pub static SERVER_WRAPPER: ServerWrapper<Server> = // ...
 /// ```
 ///
 /// # Class
@@ -406,7 +609,6 @@ macro_rules! methods {
 ///
 /// ```
 /// #[macro_use] extern crate ruru;
-/// #[macro_use] extern crate lazy_static;
 ///
 /// use ruru::{AnyObject, Class, Fixnum, Object, RString, VM};
 ///
@@ -445,17 +647,17 @@ macro_rules! methods {
 ///         let server = Server::new(host.unwrap().to_string(),
 ///                                  port.unwrap().to_i64() as u16);
 ///
-///         Class::from_existing("RubyServer").wrap_data(server, &*SERVER_WRAPPER)
+///         Class::from_existing("RubyServer").wrap_data(server, &SERVER_WRAPPER)
 ///     }
 ///
 ///     fn ruby_server_host() -> RString {
-///         let host = itself.get_data(&*SERVER_WRAPPER).host();
+///         let host = itself.get_data(&SERVER_WRAPPER).host();
 ///
 ///         RString::new(host)
 ///     }
 ///
 ///     fn ruby_server_port() -> Fixnum {
-///         let port = itself.get_data(&*SERVER_WRAPPER).port();
+///         let port = itself.get_data(&SERVER_WRAPPER).port();
 ///
 ///         Fixnum::new(port as i64)
 ///     }
@@ -489,8 +691,8 @@ macro_rules! methods {
 ///
 /// ```
 /// #[macro_use] extern crate ruru;
-/// #[macro_use] extern crate lazy_static;
 ///
+/// use std::mem;
 /// use std::ops::{Deref, DerefMut};
 ///
 /// use ruru::{AnyObject, Class, Fixnum, GC, NilClass, Object, VM};
@@ -532,6 +734,12 @@ macro_rules! methods {
 ///         for object in &data.inner {
 ///             GC::mark(object);
 ///         }
+///     },
+///
+///     // Report the memory used by the vector to `ObjectSpace.memsize_of`.
+///     // `data` is a reference to the wrapped data (`&VectorOfObjects`).
+///     size(data) {
+///         mem::size_of::<VectorOfObjects>() + data.inner.capacity() * mem::size_of::<AnyObject>()
 ///     }
 /// }
 ///
@@ -544,17 +752,17 @@ macro_rules! methods {
 ///     fn new() -> AnyObject {
 ///         let vec = VectorOfObjects::new();
 ///
-///         Class::from_existing("RustyArray").wrap_data(vec, &*VECTOR_OF_OBJECTS_WRAPPER)
+///         Class::from_existing("RustyArray").wrap_data(vec, &VECTOR_OF_OBJECTS_WRAPPER)
 ///     }
 ///
 ///     fn push(object: AnyObject) -> NilClass {
-///         itself.get_data_mut(&*VECTOR_OF_OBJECTS_WRAPPER).push(object.unwrap());
+///         itself.get_data_mut(&VECTOR_OF_OBJECTS_WRAPPER).push(object.unwrap());
 ///
 ///         NilClass::new()
 ///     }
 ///
 ///     fn length() -> Fixnum {
-///         let length = itself.get_data(&*VECTOR_OF_OBJECTS_WRAPPER).len() as i64;
+///         let length = itself.get_data(&VECTOR_OF_OBJECTS_WRAPPER).len() as i64;
 ///
 ///         Fixnum::new(length)
 ///     }
@@ -586,54 +794,183 @@ macro_rules! methods {
 /// ```
 #[macro_export]
 macro_rules! wrappable_struct {
+    // Options are passed as a tail of tokens starting with a comma, for example
+    // `, parent(WRAPPER), clone, mark(data) { ... }`. Each rule below looks for its own
+    // option in the tail and skips other tokens one by one.
     (@mark_function_pointer) => {
         None as Option<extern "C" fn(*mut $crate::types::c_void)>
     };
-    // Leading comma is the comma between `$static_name: ident` and `mark` in the main macro rule.
-    // Optional comma `$(,)*` is not allowed in the main rule, because it is
-    // followed by `$($tail: tt)*`
-    (@mark_function_pointer , mark($object: ident) $body: block) => {
+    (@mark_function_pointer , mark($object: ident) $body: block $($tail: tt)*) => {
         Some(Self::mark as extern "C" fn(*mut $crate::types::c_void))
     };
-    (@mark_function_definition $struct_name: ty) => {};
-    (@mark_function_definition $struct_name: ty, mark($object: ident) $body: expr) => {
+    (@mark_function_pointer $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@mark_function_pointer $($tail)*)
+    };
+    (@mark_function_definition $struct_name: ty;) => {};
+    (@mark_function_definition $struct_name: ty; , mark($object: ident) $body: block $($tail: tt)*) => {
         pub extern "C" fn mark(data: *mut $crate::types::c_void) {
-            let mut data = unsafe { (data as *mut $struct_name).as_mut() };
+            let mut data = unsafe { $crate::typed_data::wrapped_data::<$struct_name>(data) };
 
             if let Some(ref mut $object) = data {
                 $body
             }
         }
     };
+    (@mark_function_definition $struct_name: ty; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@mark_function_definition $struct_name; $($tail)*);
+    };
+    (@size_function_pointer $struct_name: ty;) => {
+        Some($crate::typed_data::size::<$struct_name> as extern "C" fn(*const $crate::types::c_void) -> $crate::types::size_t)
+    };
+    (@size_function_pointer $struct_name: ty; , size($object: ident) $body: block $($tail: tt)*) => {
+        Some(Self::size as extern "C" fn(*const $crate::types::c_void) -> $crate::types::size_t)
+    };
+    (@size_function_pointer $struct_name: ty; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@size_function_pointer $struct_name; $($tail)*)
+    };
+    (@size_function_definition $struct_name: ty;) => {};
+    (@size_function_definition $struct_name: ty; , size($object: ident) $body: block $($tail: tt)*) => {
+        pub extern "C" fn size(data: *const $crate::types::c_void) -> $crate::types::size_t {
+            let data = unsafe { $crate::typed_data::wrapped_data::<$struct_name>(data) };
+
+            match data.map(|data| &*data) {
+                Some($object) => {
+                    let size: usize = $body;
+
+                    size as $crate::types::size_t
+                }
+                None => 0,
+            }
+        }
+    };
+    (@size_function_definition $struct_name: ty; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@size_function_definition $struct_name; $($tail)*);
+    };
+    // Returns the parent data type and the function which converts the data to the parent struct
+    (@parent_data_type $struct_name: ty;) => {
+        (::std::ptr::null(), ::std::ptr::null_mut())
+    };
+    (@parent_data_type $struct_name: ty; , parent($parent: ident) $($tail: tt)*) => {
+        (
+            $parent.data_type_ptr(),
+            $crate::typed_data::upcast_function::<$struct_name, _, _>(&$parent),
+        )
+    };
+    (@parent_data_type $struct_name: ty; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@parent_data_type $struct_name; $($tail)*)
+    };
+    (@clone_functions $struct_name: ty, $wrapper: ident, $static_name: ident;) => {};
+    (@clone_functions $struct_name: ty, $wrapper: ident, $static_name: ident; , clone $($tail: tt)*) => {
+        impl $wrapper<$struct_name> {
+            extern "C" fn allocate(klass: $crate::types::Value) -> $crate::types::Value {
+                $crate::typed_data::allocate(klass, &$static_name)
+            }
+
+            extern "C" fn initialize_copy(
+                argc: $crate::types::Argc,
+                argv: *const $crate::AnyObject,
+                itself: $crate::AnyObject,
+            ) -> $crate::AnyObject {
+                $crate::typed_data::initialize_copy(argc, argv, itself, &$static_name)
+            }
+        }
+
+        impl $crate::typed_data::CloneableDataTypeWrapper<$struct_name> for $wrapper<$struct_name> {
+            fn allocator(&self) -> extern "C" fn($crate::types::Value) -> $crate::types::Value {
+                Self::allocate
+            }
+
+            fn initialize_copy(&self) -> $crate::types::Callback<$crate::AnyObject, $crate::AnyObject> {
+                Self::initialize_copy
+            }
+        }
+    };
+    (@clone_functions $struct_name: ty, $wrapper: ident, $static_name: ident; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@clone_functions $struct_name, $wrapper, $static_name; $($tail)*);
+    };
+    (@class_function) => {};
+    (@class_function , class($class_name: expr) $($tail: tt)*) => {
+        fn class(&self) -> Option<$crate::Class> {
+            Some($crate::Class::from_existing($class_name))
+        }
+    };
+    (@class_function $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@class_function $($tail)*);
+    };
+    (@copy_functions_function $struct_name: ty, $wrapper: ident;) => {};
+    (@copy_functions_function $struct_name: ty, $wrapper: ident; , clone $($tail: tt)*) => {
+        fn copy_functions(
+            &self,
+        ) -> Option<(
+            extern "C" fn($crate::types::Value) -> $crate::types::Value,
+            $crate::types::Callback<$crate::AnyObject, $crate::AnyObject>,
+        )> {
+            Some(($wrapper::<$struct_name>::allocate, $wrapper::<$struct_name>::initialize_copy))
+        }
+    };
+    (@copy_functions_function $struct_name: ty, $wrapper: ident; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@copy_functions_function $struct_name, $wrapper; $($tail)*);
+    };
+    (@marshal_functions $struct_name: ty, $wrapper: ident, $static_name: ident;) => {};
+    (@marshal_functions $struct_name: ty, $wrapper: ident, $static_name: ident; , marshal $($tail: tt)*) => {
+        impl $wrapper<$struct_name> {
+            extern "C" fn dump(
+                argc: $crate::types::Argc,
+                argv: *const $crate::AnyObject,
+                itself: $crate::AnyObject,
+            ) -> $crate::AnyObject {
+                $crate::typed_data::dump(argc, argv, itself, &$static_name)
+            }
+
+            extern "C" fn load(
+                argc: $crate::types::Argc,
+                argv: *const $crate::AnyObject,
+                itself: $crate::Class,
+            ) -> $crate::AnyObject {
+                $crate::typed_data::load(argc, argv, itself, &$static_name)
+            }
+        }
+
+        impl $crate::typed_data::MarshalDataTypeWrapper<$struct_name> for $wrapper<$struct_name> {
+            fn dump(&self) -> $crate::types::Callback<$crate::AnyObject, $crate::AnyObject> {
+                Self::dump
+            }
+
+            fn load(&self) -> $crate::types::Callback<$crate::Class, $crate::AnyObject> {
+                Self::load
+            }
+        }
+    };
+    (@marshal_functions $struct_name: ty, $wrapper: ident, $static_name: ident; $skipped: tt $($tail: tt)*) => {
+        wrappable_struct!(@marshal_functions $struct_name, $wrapper, $static_name; $($tail)*);
+    };
     ($struct_name: ty, $wrapper: ident, $static_name: ident $($tail: tt)*) => {
         pub struct $wrapper<T> {
             data_type: $crate::types::DataType,
             _marker: ::std::marker::PhantomData<T>,
         }
 
-        lazy_static! {
-            pub static ref $static_name: $wrapper<$struct_name> = $wrapper::new();
-        }
+        pub static $static_name: $wrapper<$struct_name> = $wrapper::<$struct_name>::new();
 
-        impl<T> $wrapper<T> {
-            fn new() -> $wrapper<T> {
-                let name = concat!("Ruru/", stringify!($struct_name));
-                let name = $crate::util::str_to_cstring(name);
-                let reserved_bytes: [*mut $crate::types::c_void; 2] = [::std::ptr::null_mut(); 2];
+        impl $wrapper<$struct_name> {
+            const fn new() -> Self {
+                let name = concat!("Ruru/", stringify!($struct_name), "\0");
 
                 let dmark = wrappable_struct!(@mark_function_pointer $($tail)*);
+                let dsize = wrappable_struct!(@size_function_pointer $struct_name; $($tail)*);
+                let (parent, upcast) = wrappable_struct!(@parent_data_type $struct_name; $($tail)*);
 
                 let data_type = $crate::types::DataType {
-                    wrap_struct_name: name.into_raw(),
-                    parent: ::std::ptr::null(),
-                    data: ::std::ptr::null_mut(),
-                    flags: $crate::types::Value::from(0),
+                    wrap_struct_name: name.as_ptr() as *const $crate::types::c_char,
+                    parent: parent,
+                    data: upcast,
+                    flags: $crate::types::Value { value: 0 },
 
                     function: $crate::types::DataTypeFunction {
                         dmark: dmark,
-                        dfree: Some($crate::typed_data::free::<T>),
-                        dsize: None,
-                        reserved: reserved_bytes,
+                        dfree: Some($crate::typed_data::free::<$struct_name>),
+                        dsize: dsize,
+                        reserved: [::std::ptr::null_mut(); 2],
                     },
                 };
 
@@ -643,7 +980,15 @@ macro_rules! wrappable_struct {
                 }
             }
 
-            wrappable_struct!(@mark_function_definition $struct_name $($tail)*);
+            // Used by child structs, because fields of the wrapper are private to the module
+            // and trait methods cannot be called in constant expressions
+            #[doc(hidden)]
+            pub const fn data_type_ptr(&self) -> *const $crate::types::DataType {
+                &self.data_type
+            }
+
+            wrappable_struct!(@mark_function_definition $struct_name; $($tail)*);
+            wrappable_struct!(@size_function_definition $struct_name; $($tail)*);
         }
 
         unsafe impl<T> Sync for $wrapper<T> {}
@@ -653,6 +998,135 @@ macro_rules! wrappable_struct {
             fn data_type(&self) -> &$crate::types::DataType {
                 &self.data_type
             }
+
+            wrappable_struct!(@class_function $($tail)*);
+            wrappable_struct!(@copy_functions_function $struct_name, $wrapper; $($tail)*);
         }
+
+        wrappable_struct!(@clone_functions $struct_name, $wrapper, $static_name; $($tail)*);
+        wrappable_struct!(@marshal_functions $struct_name, $wrapper, $static_name; $($tail)*);
+    };
+}
+
+/// Implements `TypedData::data_type()` for a struct.
+///
+/// The macro is used inside `impl TypedData`. The data type is created at compile time and
+/// stored in a `static`, so it is shared by all objects which wrap the struct.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate ruru;
+///
+/// use ruru::{Class, Object, TypedData, VM};
+///
+/// pub struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// impl TypedData for Point {
+///     fn class() -> Class {
+///         Class::from_existing("Point")
+///     }
+///
+///     typed_data_type!(Point);
+/// }
+///
+/// fn main() {
+///     # VM::init();
+///     Class::new("Point", None);
+///
+///     let point = Point { x: 1, y: 2 }.wrap();
+///
+///     assert_eq!(point.with_borrow(|point: &Point| point.x + point.y), 3);
+/// }
+/// ```
+#[macro_export]
+macro_rules! typed_data_type {
+    ($struct_name: ty) => {
+        fn data_type() -> &'static $crate::types::DataType {
+            static DATA_TYPE: $crate::codegen::StaticDataType =
+                $crate::codegen::StaticDataType::new::<$struct_name>(concat!(
+                    "Ruru/",
+                    stringify!($struct_name),
+                    "\0"
+                ));
+
+            DATA_TYPE.get()
+        }
+    };
+}
+
+/// Defines the entry point of a Ruby extension
+///
+/// The macro generates a function exported as `Init_<name>`, which is called by Ruby when the
+/// extension is loaded with `require "<name>"`. The body of the function is the initialization
+/// code of the extension (definitions of classes, methods, etc).
+///
+/// Exceptions raised and panics occurred during the initialization do not crash the process.
+/// They are raised as `LoadError` with the reason of the failure in the message, so they can be
+/// rescued by the code which loads the extension.
+///
+/// If `ractor_safe` is passed after the name, the extension is marked as safe to be used from
+/// non-main Ractors. The option is ignored by versions of Ruby without Ractors.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate ruru;
+///
+/// use ruru::{Boolean, Class, Object, RString, VM};
+///
+/// methods!(
+///    RString,
+///    itself,
+///
+///    fn string_is_blank() -> Boolean {
+///        Boolean::new(itself.to_string().chars().all(|c| c.is_whitespace()))
+///    }
+/// );
+///
+/// // Exported as `Init_string_blank`
+/// init!(string_blank, {
+///     Class::from_existing("String").define(|itself| {
+///         itself.def("blank?", string_is_blank);
+///     });
+/// });
+///
+/// // A Ractor-safe extension
+/// init!(ractor_safe_extension, ractor_safe, {
+///     Class::new("RactorSafe", None);
+/// });
+///
+/// fn main() {
+///     # VM::init();
+///     # string_blank();
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// require 'string_blank'
+///
+/// ' '.blank? == true
+/// ```
+#[macro_export]
+macro_rules! init {
+    (@entry_point $name: ident, $ractor_safe: expr, $body: block) => {
+        #[allow(non_snake_case)]
+        #[export_name = concat!("Init_", stringify!($name))]
+        pub extern "C" fn $name() {
+            $crate::codegen::init(stringify!($name), $ractor_safe, || $body);
+        }
+    };
+
+    ($name: ident, ractor_safe, $body: block) => {
+        init!(@entry_point $name, true, $body);
+    };
+
+    ($name: ident, $body: block) => {
+        init!(@entry_point $name, false, $body);
     };
 }
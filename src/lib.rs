@@ -1,16 +1,27 @@
-#[allow(unused_imports)]
+#[cfg(unix)]
+extern crate libc;
+extern crate ruby_sys;
+
+#[cfg(feature = "serde")]
 #[macro_use]
-extern crate lazy_static;
+extern crate serde as serde_lib;
 
-extern crate ruby_sys;
+#[cfg(feature = "derive")]
+extern crate ruru_derive;
+
+// Macros are declared first, so they can be used by other modules
+#[macro_use]
+pub mod dsl;
 
 mod binding;
 mod class;
 
-#[macro_use]
-pub mod dsl;
+#[doc(hidden)]
+pub mod codegen;
 
 pub mod result;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod typed_data;
 pub mod types;
 pub mod util;
@@ -19,20 +30,35 @@ pub use class::any_object::AnyObject;
 pub use class::array::Array;
 pub use class::boolean::Boolean;
 pub use class::class::Class;
+pub use class::condition_variable::ConditionVariable;
+pub use class::embedded::Embedded;
+pub use class::enumerator::Enumerator;
+pub use class::exception::Exception;
+pub use class::fiber::Fiber;
 pub use class::fixnum::Fixnum;
 pub use class::float::Float;
 pub use class::gc::GC;
 pub use class::hash::Hash;
 pub use class::integer::Integer;
+pub use class::mutex::Mutex;
 pub use class::nil_class::NilClass;
+pub use class::queue::Queue;
+pub use class::rooted::{Rooted, SendRooted};
 pub use class::rproc::Proc;
 pub use class::string::RString;
 pub use class::symbol::Symbol;
-pub use class::thread::Thread;
+pub use class::thread::{CancellationToken, FdEvents, Gvl, JoinHandle, Thread};
 pub use class::vm::VM;
 
+pub use class::traits::from_ruby::FromRuby;
+pub use class::traits::marshal_data::MarshalData;
 pub use class::traits::object::Object;
+pub use class::traits::to_ruby::ToRuby;
+pub use class::traits::typed_data::TypedData;
 pub use class::traits::verified_object::VerifiedObject;
 
+#[cfg(feature = "derive")]
+pub use ruru_derive::{ruby_methods, RubyClass};
+
 #[test]
 fn it_works() {}
@@ -1,14 +1,18 @@
+use std::any::Any;
 use std::error;
 use std::fmt::{self, Display, Formatter};
 use std::result;
 
-use Class;
+use util;
+
+use {Class, Exception, Rooted};
 
 pub type Result<T> = result::Result<T, Error>;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
     ArgumentError(String),
+    RangeError(String),
     TypeError(String),
 }
 
@@ -23,14 +27,17 @@ impl Error {
     ///
     /// # VM::init();
     /// let argument_error = Error::ArgumentError("Argument is missing".to_string());
+    /// let range_error = Error::RangeError("Value is out of range".to_string());
     /// let type_error = Error::TypeError("Wrong type".to_string());
     ///
     /// assert_eq!(argument_error.to_exception(), Class::from_existing("ArgumentError"));
+    /// assert_eq!(range_error.to_exception(), Class::from_existing("RangeError"));
     /// assert_eq!(type_error.to_exception(), Class::from_existing("TypeError"));
     /// ```
     pub fn to_exception(&self) -> Class {
         let class_name = match *self {
             Error::ArgumentError(_) => "ArgumentError",
+            Error::RangeError(_) => "RangeError",
             Error::TypeError(_) => "TypeError",
         };
 
@@ -47,7 +54,71 @@ impl Display for Error {
 impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
-            Error::ArgumentError(ref message) | Error::TypeError(ref message) => message,
+            Error::ArgumentError(ref message)
+            | Error::RangeError(ref message)
+            | Error::TypeError(ref message) => message,
+        }
+    }
+}
+
+/// Error of a thread created by `Thread::new()`
+#[derive(Debug)]
+pub enum ThreadError {
+    /// The closure of the thread panicked. Contains the payload of the panic.
+    Panic(Box<Any + Send>),
+    /// The closure of the thread raised an exception.
+    ///
+    /// The exception is protected from garbage collection while the error is alive.
+    Exception(Rooted<Exception>),
+    /// The thread has been killed before the closure is finished.
+    Killed,
+}
+
+impl Display for ThreadError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            ThreadError::Panic(ref payload) => write!(
+                f,
+                "thread panicked at '{}'",
+                util::panic_payload_to_string(&**payload)
+            ),
+            ThreadError::Exception(ref exception) => {
+                write!(f, "thread raised {}", exception.inspect())
+            }
+            ThreadError::Killed => write!(f, "thread has been killed"),
+        }
+    }
+}
+
+impl error::Error for ThreadError {
+    fn description(&self) -> &str {
+        match *self {
+            ThreadError::Panic(_) => "thread panicked",
+            ThreadError::Exception(_) => "thread raised an exception",
+            ThreadError::Killed => "thread has been killed",
+        }
+    }
+}
+
+/// Error of running a script with `Embedded`
+#[derive(Debug, PartialEq)]
+pub enum EmbeddedError {
+    /// The VM has already been initialized by `VM::init()` or by another `Embedded` script.
+    AlreadyInitialized,
+}
+
+impl Display for EmbeddedError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            EmbeddedError::AlreadyInitialized => write!(f, "Ruby VM has already been initialized"),
+        }
+    }
+}
+
+impl error::Error for EmbeddedError {
+    fn description(&self) -> &str {
+        match *self {
+            EmbeddedError::AlreadyInitialized => "Ruby VM has already been initialized",
         }
     }
 }
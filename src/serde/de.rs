@@ -0,0 +1,259 @@
+use std::vec;
+
+use serde_lib::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use result::{Error, Result};
+use types::ValueType;
+
+use {AnyObject, Array, Boolean, FromRuby, Hash, Object, RString, Symbol};
+
+/// Deserializer of Rust values from Ruby objects
+///
+/// Usually there is no need to use it directly, see `ruru::serde::from_ruby()`.
+pub struct Deserializer {
+    object: AnyObject,
+}
+
+impl Deserializer {
+    /// Creates a new deserializer for the given object.
+    pub fn new(object: AnyObject) -> Self {
+        Deserializer { object: object }
+    }
+
+    fn unsupported_type(&self) -> Error {
+        let class_name = self.object.class().send("name", None);
+        let class_name = RString::from(class_name.value()).to_string();
+
+        Error::TypeError(format!(
+            "Cannot deserialize an object of class {}",
+            class_name
+        ))
+    }
+
+    fn string_value(&self) -> Option<String> {
+        match self.object.ty() {
+            ValueType::RString => Some(RString::from(self.object.value()).to_string()),
+            ValueType::Symbol => Some(Symbol::from(self.object.value()).to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.object.ty() {
+            ValueType::Nil => visitor.visit_unit(),
+            ValueType::True | ValueType::False => {
+                visitor.visit_bool(Boolean::from(self.object.value()).to_bool())
+            }
+            ValueType::Fixnum | ValueType::Bignum => match i64::from_ruby(&self.object) {
+                Ok(value) => visitor.visit_i64(value),
+                Err(_) => visitor.visit_u64(u64::from_ruby(&self.object)?),
+            },
+            ValueType::Float => visitor.visit_f64(f64::from_ruby(&self.object)?),
+            ValueType::RString | ValueType::Symbol => match self.string_value() {
+                Some(string) => visitor.visit_string(string),
+                None => Err(self.unsupported_type()),
+            },
+            ValueType::Array => {
+                let array = Array::from(self.object.value());
+                let items: Vec<AnyObject> = array.into_iter().collect();
+
+                visitor.visit_seq(SeqAccess::new(items))
+            }
+            ValueType::Hash => {
+                let hash = Hash::from(self.object.value());
+                let mut pairs = Vec::with_capacity(hash.length());
+
+                hash.each(|key, value| pairs.push((key, value)));
+
+                visitor.visit_map(MapAccess::new(pairs))
+            }
+            _ => Err(self.unsupported_type()),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.object.ty() == ValueType::RString {
+            let string = RString::from(self.object.value());
+
+            visitor.visit_bytes(string.to_bytes_unchecked())
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.object.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if let Some(variant) = self.string_value() {
+            return visitor.visit_enum(variant.into_deserializer());
+        }
+
+        if self.object.ty() == ValueType::Hash {
+            let hash = Hash::from(self.object.value());
+
+            if hash.length() == 1 {
+                let mut pair = None;
+
+                hash.each(|key, value| pair = Some((key, value)));
+
+                if let Some((variant, content)) = pair {
+                    return visitor.visit_enum(EnumAccess::new(variant, content));
+                }
+            }
+        }
+
+        Err(Error::TypeError(
+            "Enum must be a String, a Symbol or a Hash with a single pair".to_string(),
+        ))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess {
+    items: vec::IntoIter<AnyObject>,
+}
+
+impl SeqAccess {
+    fn new(items: Vec<AnyObject>) -> Self {
+        SeqAccess {
+            items: items.into_iter(),
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.items.next() {
+            Some(item) => seed.deserialize(Deserializer::new(item)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapAccess {
+    pairs: vec::IntoIter<(AnyObject, AnyObject)>,
+    next_value: Option<AnyObject>,
+}
+
+impl MapAccess {
+    fn new(pairs: Vec<(AnyObject, AnyObject)>) -> Self {
+        MapAccess {
+            pairs: pairs.into_iter(),
+            next_value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.pairs.next() {
+            Some((key, value)) => {
+                self.next_value = Some(value);
+
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.next_value.take() {
+            Some(value) => seed.deserialize(Deserializer::new(value)),
+            None => Err(Error::ArgumentError(
+                "Value of a map is deserialized before its key".to_string(),
+            )),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.pairs.len())
+    }
+}
+
+struct EnumAccess {
+    variant: AnyObject,
+    content: AnyObject,
+}
+
+impl EnumAccess {
+    fn new(variant: AnyObject, content: AnyObject) -> Self {
+        EnumAccess {
+            variant: variant,
+            content: content,
+        }
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Deserializer)> {
+        let variant = seed.deserialize(Deserializer::new(self.variant))?;
+
+        Ok((variant, Deserializer::new(self.content)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _length: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+use serde_lib::{de, ser};
+
+use result::Error;
+
+impl ser::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error::TypeError(message.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error::TypeError(message.to_string())
+    }
+
+    fn invalid_value(unexpected: de::Unexpected, expected: &de::Expected) -> Self {
+        Error::RangeError(format!(
+            "invalid value: {}, expected {}",
+            unexpected, expected
+        ))
+    }
+
+    fn invalid_length(length: usize, expected: &de::Expected) -> Self {
+        Error::ArgumentError(format!("invalid length {}, expected {}", length, expected))
+    }
+
+    fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+        Error::ArgumentError(format!(
+            "unknown variant `{}`, expected one of {:?}",
+            variant, expected
+        ))
+    }
+
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Error::ArgumentError(format!(
+            "unknown field `{}`, expected one of {:?}",
+            field, expected
+        ))
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::ArgumentError(format!("missing field `{}`", field))
+    }
+
+    fn duplicate_field(field: &'static str) -> Self {
+        Error::ArgumentError(format!("duplicate field `{}`", field))
+    }
+}
@@ -0,0 +1,256 @@
+//! Serialization of Rust values to Ruby objects and back using [serde](https://serde.rs)
+//!
+//! This module is available only when the `serde` feature is enabled:
+//!
+//! ```toml
+//! ruru = { version = "0.9", features = ["serde"] }
+//! ```
+//!
+//! Rust values are mapped to Ruby objects as follows:
+//!
+//!  - `bool` to `true` or `false`;
+//!  - integers to `Integer`, floats to `Float`;
+//!  - `char`, `String`, `&str` to UTF-8 `String`;
+//!  - bytes to `String` with `ASCII-8BIT` encoding;
+//!  - `None`, `()` and unit structs to `nil`;
+//!  - sequences and tuples to `Array`;
+//!  - maps to `Hash`;
+//!  - structs to `Hash` with field names as keys;
+//!  - unit variants of enums to the name of the variant;
+//!  - other variants to `Hash` with a single pair `{ variant_name => content }`.
+//!
+//! Field names and variant names are converted to `Symbol`s by default. Use `Keys::String`
+//! to convert them to `String`s instead. Deserialization accepts both.
+//!
+//! `to_bytes()` and `from_bytes()` store values in Ruby `Marshal` format, which is useful for
+//! implementing `MarshalData`.
+//!
+//! # Examples
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate ruru;
+//!
+//! use ruru::serde::{from_ruby, to_ruby};
+//! use ruru::{Fixnum, Hash, Object, Symbol, VM};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Server {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! fn main() {
+//!     # VM::init();
+//!     let server = Server { host: "localhost".to_string(), port: 8080 };
+//!
+//!     let hash = to_ruby(&server).unwrap().try_convert_to::<Hash>().unwrap();
+//!     let port = hash.at(&Symbol::new("port")).try_convert_to::<Fixnum>();
+//!
+//!     assert_eq!(port, Ok(Fixnum::new(8080)));
+//!     assert_eq!(from_ruby::<Server>(&hash.to_any_object()), Ok(server));
+//! }
+//! ```
+//!
+//! Ruby:
+//!
+//! ```ruby
+//! server = { host: 'localhost', port: 8080 }
+//!
+//! server[:port] == 8080
+//! ```
+
+mod de;
+mod error;
+mod ser;
+
+use serde_lib::de::DeserializeOwned;
+use serde_lib::ser::Serialize;
+
+use result::{Error, Result};
+
+use {AnyObject, Class, Object, RString};
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+/// Type of keys for struct fields and enum variants
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Keys {
+    String,
+    Symbol,
+}
+
+/// Serializes a Rust value to a Ruby object using `Symbol` keys for struct fields.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use ruru::serde::to_ruby;
+/// use ruru::{Array, Object, VM};
+/// # VM::init();
+///
+/// let array = to_ruby(&vec![(1, "one"), (2, "two")]).unwrap();
+///
+/// assert_eq!(array.try_convert_to::<Array>().unwrap().length(), 2);
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// [[1, 'one'], [2, 'two']]
+/// ```
+pub fn to_ruby<T: Serialize + ?Sized>(value: &T) -> Result<AnyObject> {
+    to_ruby_with_keys(value, Keys::Symbol)
+}
+
+/// Serializes a Rust value to a Ruby object using the given type of keys for struct fields.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate ruru;
+///
+/// use ruru::serde::{to_ruby_with_keys, Keys};
+/// use ruru::{Hash, Object, RString, VM};
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// fn main() {
+///     # VM::init();
+///     let user = User { name: "Jane".to_string() };
+///
+///     let hash = to_ruby_with_keys(&user, Keys::String).unwrap();
+///     let hash = hash.try_convert_to::<Hash>().unwrap();
+///
+///     let name = hash.at(&RString::new("name")).try_convert_to::<RString>().unwrap();
+///
+///     assert_eq!(name.to_str(), "Jane");
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// user = { 'name' => 'Jane' }
+///
+/// user['name'] == 'Jane'
+/// ```
+pub fn to_ruby_with_keys<T: Serialize + ?Sized>(value: &T, keys: Keys) -> Result<AnyObject> {
+    value.serialize(&Serializer::new(keys))
+}
+
+/// Deserializes a Rust value from a Ruby object.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use ruru::serde::from_ruby;
+/// use ruru::{Fixnum, Hash, Object, RString, VM};
+/// # VM::init();
+///
+/// let mut hash = Hash::new();
+///
+/// hash.store(RString::new("one"), Fixnum::new(1));
+///
+/// let map = from_ruby::<HashMap<String, u8>>(&hash.to_any_object()).unwrap();
+///
+/// assert_eq!(map.get("one"), Some(&1));
+/// ```
+pub fn from_ruby<T: DeserializeOwned>(object: &AnyObject) -> Result<T> {
+    T::deserialize(Deserializer::new(object.clone()))
+}
+
+/// Serializes a Rust value to bytes in Ruby `Marshal` format.
+///
+/// The value is converted to a Ruby object with `to_ruby()` and dumped with `Marshal.dump`.
+/// It can be used to implement `MarshalData` for serializable structs.
+///
+/// # Examples
+///
+/// ```
+/// use ruru::serde::{from_bytes, to_bytes};
+/// use ruru::VM;
+/// # VM::init();
+///
+/// let bytes = to_bytes(&(1, "one")).unwrap();
+///
+/// assert_eq!(from_bytes::<(u8, String)>(&bytes), Ok((1, "one".to_string())));
+/// assert!(from_bytes::<(u8, String)>(&bytes[..2]).is_err());
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// bytes = Marshal.dump([1, 'one'])
+///
+/// Marshal.load(bytes) == [1, 'one']
+/// ```
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let object = to_ruby(value)?;
+    let bytes = Class::from_existing("Marshal").send("dump", Some(&[object]));
+    let bytes = bytes.try_convert_to::<RString>()?;
+
+    Ok(bytes.to_bytes_unchecked().to_vec())
+}
+
+/// Deserializes a Rust value from bytes in Ruby `Marshal` format.
+///
+/// The bytes are loaded with `Marshal.load` and the result is converted with `from_ruby()`.
+/// Returns `ArgumentError` if the bytes are not valid `Marshal` data.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::result::Result;
+/// use ruru::serde::{from_bytes, to_bytes};
+/// use ruru::{AnyObject, Class, MarshalData, Object, VM};
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// impl MarshalData for Server {
+///     fn dump(&self) -> Result<Vec<u8>> {
+///         to_bytes(self)
+///     }
+///
+///     fn load(bytes: &[u8]) -> Result<Self> {
+///         from_bytes(bytes)
+///     }
+/// }
+///
+/// wrappable_struct!(Server, ServerWrapper, SERVER_WRAPPER, marshal);
+///
+/// fn main() {
+///     # VM::init();
+///     let data_class = Class::from_existing("Data");
+///
+///     Class::new("Server", Some(&data_class)).define_marshal(&SERVER_WRAPPER);
+/// }
+/// ```
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let bytes = RString::from_bytes(bytes).to_any_object();
+    let object = Class::from_existing("Marshal")
+        .try_send("load", Some(&[bytes]))
+        .map_err(|exception| Error::ArgumentError(exception.message()))?;
+
+    from_ruby(&object)
+}
@@ -0,0 +1,377 @@
+use serde_lib::ser::{self, Serialize};
+
+use result::{Error, Result};
+
+use super::Keys;
+use {AnyObject, Array, Hash, NilClass, Object, RString, Symbol, ToRuby};
+
+/// Serializer of Rust values to Ruby objects
+///
+/// Usually there is no need to use it directly, see `ruru::serde::to_ruby()`.
+pub struct Serializer {
+    keys: Keys,
+}
+
+impl Serializer {
+    /// Creates a new serializer which uses the given type of keys for struct fields and
+    /// enum variants.
+    pub fn new(keys: Keys) -> Self {
+        Serializer { keys: keys }
+    }
+
+    fn key(&self, name: &str) -> AnyObject {
+        match self.keys {
+            Keys::String => RString::new_utf8(name).to_any_object(),
+            Keys::Symbol => Symbol::new(name).to_any_object(),
+        }
+    }
+
+    fn variant(&self, variant: &str, content: AnyObject) -> AnyObject {
+        let mut hash = Hash::new();
+
+        hash.store(self.key(variant), content);
+
+        hash.to_any_object()
+    }
+}
+
+impl<'a> ser::Serializer for &'a Serializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray<'a>;
+    type SerializeTuple = SerializeArray<'a>;
+    type SerializeTupleStruct = SerializeArray<'a>;
+    type SerializeTupleVariant = SerializeArray<'a>;
+    type SerializeMap = SerializeHash<'a>;
+    type SerializeStruct = SerializeHash<'a>;
+    type SerializeStructVariant = SerializeHash<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_char(self, value: char) -> Result<AnyObject> {
+        Ok(value.to_string().to_ruby())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<AnyObject> {
+        Ok(value.to_ruby())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<AnyObject> {
+        Ok(RString::from_bytes(value).to_any_object())
+    }
+
+    fn serialize_none(self) -> Result<AnyObject> {
+        Ok(NilClass::new().to_any_object())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<AnyObject> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<AnyObject> {
+        Ok(NilClass::new().to_any_object())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<AnyObject> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<AnyObject> {
+        Ok(self.key(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<AnyObject> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<AnyObject> {
+        let content = value.serialize(self)?;
+
+        Ok(self.variant(variant, content))
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<SerializeArray<'a>> {
+        Ok(SerializeArray::new(self, length.unwrap_or(0), None))
+    }
+
+    fn serialize_tuple(self, length: usize) -> Result<SerializeArray<'a>> {
+        Ok(SerializeArray::new(self, length, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        length: usize,
+    ) -> Result<SerializeArray<'a>> {
+        Ok(SerializeArray::new(self, length, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<SerializeArray<'a>> {
+        Ok(SerializeArray::new(self, length, Some(variant)))
+    }
+
+    fn serialize_map(self, _length: Option<usize>) -> Result<SerializeHash<'a>> {
+        Ok(SerializeHash::new(self, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _length: usize) -> Result<SerializeHash<'a>> {
+        Ok(SerializeHash::new(self, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _length: usize,
+    ) -> Result<SerializeHash<'a>> {
+        Ok(SerializeHash::new(self, Some(variant)))
+    }
+}
+
+/// Serializer of sequences, tuples and tuple variants to `Array`
+pub struct SerializeArray<'a> {
+    serializer: &'a Serializer,
+    array: Array,
+    variant: Option<&'static str>,
+}
+
+impl<'a> SerializeArray<'a> {
+    fn new(serializer: &'a Serializer, capacity: usize, variant: Option<&'static str>) -> Self {
+        SerializeArray {
+            serializer: serializer,
+            array: Array::with_capacity(capacity),
+            variant: variant,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = value.serialize(self.serializer)?;
+
+        self.array.push(value);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AnyObject> {
+        let array = self.array.to_any_object();
+
+        match self.variant {
+            Some(variant) => Ok(self.serializer.variant(variant, array)),
+            None => Ok(array),
+        }
+    }
+}
+
+impl<'a> ser::SerializeSeq for SerializeArray<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for SerializeArray<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for SerializeArray<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for SerializeArray<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+/// Serializer of maps, structs and struct variants to `Hash`
+pub struct SerializeHash<'a> {
+    serializer: &'a Serializer,
+    hash: Hash,
+    next_key: Option<AnyObject>,
+    variant: Option<&'static str>,
+}
+
+impl<'a> SerializeHash<'a> {
+    fn new(serializer: &'a Serializer, variant: Option<&'static str>) -> Self {
+        SerializeHash {
+            serializer: serializer,
+            hash: Hash::new(),
+            next_key: None,
+            variant: variant,
+        }
+    }
+
+    fn store<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        let key = self.serializer.key(key);
+        let value = value.serialize(self.serializer)?;
+
+        self.hash.store(key, value);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AnyObject> {
+        let hash = self.hash.to_any_object();
+
+        match self.variant {
+            Some(variant) => Ok(self.serializer.variant(variant, hash)),
+            None => Ok(hash),
+        }
+    }
+}
+
+impl<'a> ser::SerializeMap for SerializeHash<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.next_key = Some(key.serialize(self.serializer)?);
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.next_key.take().ok_or_else(|| {
+            Error::ArgumentError("Value of a map is serialized before its key".to_string())
+        })?;
+        let value = value.serialize(self.serializer)?;
+
+        self.hash.store(key, value);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for SerializeHash<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.store(key, value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for SerializeHash<'a> {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.store(key, value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
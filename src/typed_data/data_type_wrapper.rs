@@ -1,5 +1,49 @@
-use types::DataType;
+use types::{Callback, DataType, Value};
+
+use {AnyObject, Class, MarshalData};
 
 pub trait DataTypeWrapper<T> {
     fn data_type(&self) -> &DataType;
+
+    /// Returns the class set with the `class` option of `wrappable_struct!`.
+    ///
+    /// `Class::wrap_data()` raises `TypeError` if the data is wrapped into an object of another
+    /// class which does not inherit from it.
+    fn class(&self) -> Option<Class> {
+        None
+    }
+
+    // Returns the allocator and `initialize_copy` of wrappers with the `clone` option, which
+    // are defined for classes of the objects by `Class::wrap_data()`
+    #[doc(hidden)]
+    fn copy_functions(
+        &self,
+    ) -> Option<(
+        extern "C" fn(Value) -> Value,
+        Callback<AnyObject, AnyObject>,
+    )> {
+        None
+    }
+}
+
+/// Wrappers created by `wrappable_struct!` with the `clone` option
+///
+/// See `Class::define_copy()`.
+pub trait CloneableDataTypeWrapper<T: Clone>: DataTypeWrapper<T> {
+    /// Returns an allocator which creates objects without wrapped data.
+    fn allocator(&self) -> extern "C" fn(Value) -> Value;
+
+    /// Returns an `initialize_copy` method which wraps a clone of the original data.
+    fn initialize_copy(&self) -> Callback<AnyObject, AnyObject>;
+}
+
+/// Wrappers created by `wrappable_struct!` with the `marshal` option
+///
+/// See `Class::define_marshal()`.
+pub trait MarshalDataTypeWrapper<T: MarshalData>: DataTypeWrapper<T> {
+    /// Returns a `_dump` method which converts the wrapped data to a `String`.
+    fn dump(&self) -> Callback<AnyObject, AnyObject>;
+
+    /// Returns a `_load` singleton method which wraps the data loaded from a `String`.
+    fn load(&self) -> Callback<Class, AnyObject>;
 }
@@ -1,10 +1,113 @@
 mod data_type_wrapper;
 
-use types::c_void;
+use std::ptr;
 
-pub use self::data_type_wrapper::DataTypeWrapper;
+use binding::{class, vm};
+use codegen;
+use types::{c_void, size_t, Argc, Value};
+
+use {AnyObject, Class, MarshalData, Object, RString};
+
+pub use self::data_type_wrapper::{
+    CloneableDataTypeWrapper, DataTypeWrapper, MarshalDataTypeWrapper,
+};
+pub use class::traits::typed_data::{Ref, RefMut};
 
 pub extern "C" fn free<T: Sized>(data: *mut c_void) {
-    // Memory is freed when the box goes out of the scope
-    unsafe { Box::from_raw(data as *mut T) };
+    class::free_wrapped_data::<T>(data);
+}
+
+pub extern "C" fn size<T: Sized>(_data: *const c_void) -> size_t {
+    class::wrapped_data_size::<T>() as size_t
+}
+
+// Used by `mark` and `size` functions of `wrappable_struct!`, which receive data with a header
+#[doc(hidden)]
+pub unsafe fn wrapped_data<'a, T>(data: *const c_void) -> Option<&'a mut T> {
+    if data.is_null() {
+        None
+    } else {
+        Some(class::wrapped_data(data as *mut c_void))
+    }
+}
+
+// The function is stored in the `data` field of the data type of a child struct. It converts
+// pointers to the child struct to pointers to the parent struct (see `class::get_data()`).
+pub const fn upcast_function<T: AsMut<P>, P, W: DataTypeWrapper<P>>(_parent: &W) -> *mut c_void {
+    upcast::<T, P> as extern "C" fn(*mut c_void) -> *mut c_void as *mut c_void
+}
+
+extern "C" fn upcast<T: AsMut<P>, P>(data: *mut c_void) -> *mut c_void {
+    let data = unsafe { &mut *(data as *mut T) };
+
+    data.as_mut() as *mut P as *mut c_void
+}
+
+// Objects are allocated without data, which is set later in `initialize_copy`. MRI does not
+// call `dmark` and `dfree` functions for objects with `NULL` data.
+pub fn allocate<T>(klass: Value, wrapper: &DataTypeWrapper<T>) -> Value {
+    class::wrap_typed_data(klass, ptr::null_mut(), wrapper.data_type())
+}
+
+pub fn initialize_copy<T: Clone>(
+    argc: Argc,
+    argv: *const AnyObject,
+    itself: AnyObject,
+    wrapper: &DataTypeWrapper<T>,
+) -> AnyObject {
+    let arguments = codegen::parse_arguments(argc, argv, 1);
+    let original = &arguments[0];
+
+    // `Object#initialize_copy` checks that the copy is not frozen and has the same class
+    vm::call_super(&[original.value()]);
+
+    // Copies of objects without data also have no data
+    if *original == itself
+        || class::check_typed_data(original.value(), wrapper.data_type()).is_null()
+    {
+        return itself;
+    }
+
+    let data = original.get_data(wrapper).clone();
+    let previous_data = class::check_typed_data(itself.value(), wrapper.data_type());
+
+    let data = class::new_wrapped_data(data, wrapper.data_type());
+
+    class::set_typed_data(itself.value(), data);
+
+    if !previous_data.is_null() {
+        free::<T>(previous_data);
+    }
+
+    itself
+}
+
+// The `level` argument of `_dump` is ignored
+pub fn dump<T: MarshalData>(
+    _argc: Argc,
+    _argv: *const AnyObject,
+    itself: AnyObject,
+    wrapper: &DataTypeWrapper<T>,
+) -> AnyObject {
+    match itself.get_data(wrapper).dump() {
+        Ok(bytes) => RString::from_bytes(&bytes).to_any_object(),
+        Err(error) => codegen::raise(&error),
+    }
+}
+
+pub fn load<T: MarshalData>(
+    argc: Argc,
+    argv: *const AnyObject,
+    itself: Class,
+    wrapper: &DataTypeWrapper<T>,
+) -> AnyObject {
+    let arguments = codegen::parse_arguments(argc, argv, 1);
+    let data = arguments[0]
+        .try_convert_to::<RString>()
+        .and_then(|bytes| T::load(bytes.to_bytes_unchecked()));
+
+    match data {
+        Ok(data) => itself.wrap_data(data, wrapper),
+        Err(error) => codegen::raise(&error),
+    }
 }
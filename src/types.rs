@@ -1,10 +1,22 @@
+use std::marker::PhantomData;
+
 use AnyObject;
 
-pub use ruby_sys::types::{c_char, c_int, c_long, c_void, size_t, Argc, CallbackMutPtr,
-                          CallbackPtr, Id, InternalValue, RbDataType as DataType,
-                          RbDataTypeFunction as DataTypeFunction, SignedValue, Value, ValueType};
+pub use ruby_sys::types::{
+    c_char, c_int, c_long, c_void, size_t, Argc, CallbackMutPtr, CallbackPtr, Id, InternalValue,
+    RbDataType as DataType, RbDataTypeFunction as DataTypeFunction, SignedValue, Value, ValueType,
+};
 
 #[cfg(unix)]
 pub use ruby_sys::types::RawFd;
 
 pub type Callback<I, O> = extern "C" fn(Argc, *const AnyObject, I) -> O;
+pub type VariableGetter<O> = extern "C" fn(Id, *mut c_void) -> O;
+pub type VariableSetter = extern "C" fn(AnyObject, Id, *mut c_void);
+
+/// Marker which makes Ruby objects `!Send` and `!Sync`
+///
+/// Ruby objects can be used only by threads which hold GVL. The marker prevents moving them to
+/// closures which are run without GVL or in other threads (see `Thread::call_without_gvl()` and
+/// `Thread::new()`). Use `SendRooted` to share objects between threads.
+pub type NotSend = PhantomData<*mut ()>;
@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::ffi::{CStr, CString};
 use std::ptr;
 
@@ -66,3 +67,13 @@ where
 pub unsafe fn ptr_to_data<R>(ptr: *mut c_void) -> R {
     *Box::from_raw(ptr as *mut R)
 }
+
+pub fn panic_payload_to_string(payload: &(Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<Any>".to_string()
+    }
+}